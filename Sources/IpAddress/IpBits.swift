@@ -6,11 +6,12 @@ import BigInt
 //import IpVersion from './ip_version';
 //
 
-typealias ToString = (_ source: IpBits, _ num: BigUInt) -> String;
+typealias ToString = (_ source: IpBits, _ num: UInt128) -> String;
 
 
 // #[derive(Debug, Clone)]
-public class IpBits {
+// Immutable and shared by all the addresses of a family.
+public final class IpBits {
   let version: IpVersion;
   let vt_as_compressed_string: ToString;
   let vt_as_uncompressed_string: ToString;
@@ -19,7 +20,8 @@ public class IpBits {
   let dns_bits: UInt8;
   let rev_domain: String;
   let part_mod: Int;
-  let host_ofs: BigUInt; // ipv4=1, ipv6=0
+  let host_ofs: UInt128; // ipv4=1, ipv6=0
+  let all_ones: UInt128; // ipv4=0xffffffff, ipv6=2**128-1
   
   
   init(version: IpVersion,
@@ -27,7 +29,7 @@ public class IpBits {
        vt_as_uncompressed_string: @escaping ToString,
        bits: UInt8, part_bits: UInt8,
        dns_bits: UInt8, rev_domain: String,
-       part_mod: Int, host_ofs: BigUInt) {
+       part_mod: Int, host_ofs: UInt128) {
     self.version = version
     self.vt_as_compressed_string = vt_as_compressed_string
     self.vt_as_uncompressed_string = vt_as_uncompressed_string
@@ -37,6 +39,7 @@ public class IpBits {
     self.rev_domain = rev_domain
     self.part_mod = part_mod
     self.host_ofs = host_ofs
+    self.all_ones = UInt128.mask(Int(bits))
   }
   
   func clone() -> IpBits {
@@ -53,26 +56,62 @@ public class IpBits {
     return self;
   }
   
-  public func parts(_ bu: BigUInt)-> [UInt] {
+  public func parts(_ num: UInt128)-> [UInt] {
     var vec = [UInt]();
-    var my = bu;
-    let part_mod = BigUInt(1) << Int(self.part_bits);// - BigUInt::one();
+    var my = num;
+    let part_mask = UInt128.mask(Int(self.part_bits));
     for _ in 1...(self.bits / self.part_bits) {
-      // console.log("parts-1:", my, part_mod, my.mod(part_mod), my.mod(part_mod).toString());
-      let tmp = String(my % part_mod)
-      let itmp = UInt(tmp)
-      vec.append(itmp!)
+      vec.append(UInt((my & part_mask).lo))
       my = my >> Int(self.part_bits);
     }
     // console.log("parts:", vec);
     return Array(vec.reversed());
   }
+  public func parts(_ bu: BigUInt)-> [UInt] {
+    return self.parts(UInt128(bu)!);
+  }
   
+  public func as_compressed_string(_ num: UInt128) -> String {
+    return (self.vt_as_compressed_string)(self, num);
+  }
   public func as_compressed_string(_ bu: BigUInt) -> String {
-    return (self.vt_as_compressed_string)(self, bu);
+    return self.as_compressed_string(UInt128(bu)!);
+  }
+  public func as_uncompressed_string(_ num: UInt128) -> String {
+    return (self.vt_as_uncompressed_string)(self, num);
   }
   public func as_uncompressed_string(_ bu: BigUInt) -> String {
-    return (self.vt_as_uncompressed_string)(self, bu);
+    return self.as_uncompressed_string(UInt128(bu)!);
+  }
+  //  The RFC 5952 text of an IPv6 address, IPv4 addresses
+  //  have only one text form.
+  public func as_canonical_string(_ num: UInt128) -> String {
+    switch (self.version) {
+    case IpVersion.V4: return IpBits.ipv4_as_compressed(self, num);
+    case IpVersion.V6: return IpBits.ipv6_as_canonical(self, num);
+    }
+  }
+  
+  //  The text of +num+ in the given group format, see
+  //  FormatOptions. The compressed and expanded formats are
+  //  the ones of vt_as_compressed_string and
+  //  vt_as_uncompressed_string.
+  public func as_formatted_string(_ num: UInt128, _ groups: GroupFormat,
+                                  _ uppercase: Bool, _ embedded_ipv4: Bool) -> String {
+    var ret: String;
+    if (embedded_ipv4 && self.version == IpVersion.V6) {
+      ret = IpBits.ipv6_as_embedded_ipv4(self, num, groups);
+    } else {
+      switch (groups) {
+      case GroupFormat.compressed: ret = self.as_compressed_string(num);
+      case GroupFormat.canonical: ret = self.as_canonical_string(num);
+      case GroupFormat.expanded: ret = self.as_uncompressed_string(num);
+      }
+    }
+    if (uppercase) {
+      return ret.uppercased();
+    }
+    return ret;
   }
   
   public func dns_part_format(_ i: UInt) -> String {
@@ -82,45 +121,37 @@ public class IpBits {
     }
   }
   
-  static var _v4 : IpBits?;
+  static let _v4 = IpBits(
+    version: IpVersion.V4,
+    vt_as_compressed_string: IpBits.ipv4_as_compressed,
+    vt_as_uncompressed_string: IpBits.ipv4_as_compressed,
+    bits: 32,
+    part_bits: 8,
+    dns_bits: 8,
+    rev_domain: "in-addr.arpa",
+    part_mod: 1 << 8,
+    host_ofs: UInt128.one
+  );
   public class func v4() -> IpBits {
-    if (IpBits._v4 != nil) {
-      return IpBits._v4!;
-    }
-    IpBits._v4 = IpBits(
-      version: IpVersion.V4,
-      vt_as_compressed_string: IpBits.ipv4_as_compressed,
-      vt_as_uncompressed_string: IpBits.ipv4_as_compressed,
-      bits: 32,
-      part_bits: 8,
-      dns_bits: 8,
-      rev_domain: "in-addr.arpa",
-      part_mod: 1 << 8,
-      host_ofs: BigUInt(1)
-    );
-    return IpBits._v4!;
+    return IpBits._v4;
   }
   
-  static var _v6 : IpBits?;
+  static let _v6 = IpBits(
+    version: IpVersion.V6,
+    vt_as_compressed_string: IpBits.ipv6_as_compressed,
+    vt_as_uncompressed_string: IpBits.ipv6_as_uncompressed,
+    bits: 128,
+    part_bits: 16,
+    dns_bits: 4,
+    rev_domain: "ip6.arpa",
+    part_mod: 1 << 16,
+    host_ofs: UInt128.zero
+  );
   public class func v6() -> IpBits {
-    if (IpBits._v6 != nil) {
-      return IpBits._v6!;
-    }
-    IpBits._v6 = IpBits(
-      version: IpVersion.V6,
-      vt_as_compressed_string: IpBits.ipv6_as_compressed,
-      vt_as_uncompressed_string: IpBits.ipv6_as_uncompressed,
-      bits: 128,
-      part_bits: 16,
-      dns_bits: 4,
-      rev_domain: "ip6.arpa",
-      part_mod: 1 << 16,
-      host_ofs: BigUInt(0)
-    );
-    return IpBits._v6!;
+    return IpBits._v6;
   }
   
-  class func ipv4_as_compressed(_ ip_bits: IpBits, _ host_address: BigUInt) -> String {
+  class func ipv4_as_compressed(_ ip_bits: IpBits, _ host_address: UInt128) -> String {
     var ret = "";
     var sep = "";
     for part in ip_bits.parts(host_address) {
@@ -131,17 +162,31 @@ public class IpBits {
     return ret;
   }
   
-  class func ipv6_as_compressed(_ ip_bits: IpBits, _ host_address: BigUInt) -> String {
+  class func ipv6_as_compressed(_ ip_bits: IpBits, _ host_address: UInt128) -> String {
+    return IpBits.ipv6_as_compressed(ip_bits, host_address, 1);
+  }
+  //  RFC 5952 section 4.2: "::" never stands for a single
+  //  zero group, on a tie the leftmost run is compressed.
+  class func ipv6_as_canonical(_ ip_bits: IpBits, _ host_address: UInt128) -> String {
+    return IpBits.ipv6_as_compressed(ip_bits, host_address, 2);
+  }
+  //  Replaces the first longest run of zero groups, if it is
+  //  at least +min_run+ groups long, by "::".
+  class func ipv6_as_compressed(_ ip_bits: IpBits, _ host_address: UInt128, _ min_run: Int) -> String {
     //println!("ipv6_as_compressed:{}", host_address);
+    return IpBits.ipv6_groups_compressed(ip_bits.parts(host_address), min_run);
+  }
+  class func ipv6_groups_compressed(_ parts: [UInt], _ min_run: Int) -> String {
     var ret = "";
     var colon = "";
     var done = false;
-    for rle in Rle<Int>.code(ip_bits.parts(host_address)) {
+    for rle in Rle<Int>.code(parts) {
+      let compress = rle.part == 0 && rle.max && rle.cnt >= min_run;
       for _ in 1...rle.cnt {
-        if (done || !(rle.part == 0 && rle.max)) {
+        if (done || !compress) {
           ret += "\(colon)\(String(rle.part, radix: 16))";
           colon = ":";
-        } else if (rle.part == 0 && rle.max) {
+        } else {
           ret += "::";
           colon = "";
           done = true;
@@ -151,10 +196,13 @@ public class IpBits {
     }
     return ret;
   }
-  class func ipv6_as_uncompressed(_ ip_bits: IpBits, _ host_address: BigUInt) -> String {
+  class func ipv6_as_uncompressed(_ ip_bits: IpBits, _ host_address: UInt128) -> String {
+    return IpBits.ipv6_groups_uncompressed(ip_bits.parts(host_address));
+  }
+  class func ipv6_groups_uncompressed(_ parts: [UInt]) -> String {
     var ret = "";
     var sep = "";
-    for part in ip_bits.parts(host_address) {
+    for part in parts {
       ret += sep;
       let tmp = String((0x10000 + part), radix: 16);
       ret += String(tmp.dropFirst(1))
@@ -162,6 +210,21 @@ public class IpBits {
     }
     return ret;
   }
+  //  The first six groups followed by the last 32 bits
+  //  as IPv4 address.
+  class func ipv6_as_embedded_ipv4(_ ip_bits: IpBits, _ host_address: UInt128, _ groups: GroupFormat) -> String {
+    let parts = Array(ip_bits.parts(host_address).prefix(6));
+    var upper: String;
+    switch (groups) {
+    case GroupFormat.compressed: upper = IpBits.ipv6_groups_compressed(parts, 1);
+    case GroupFormat.canonical: upper = IpBits.ipv6_groups_compressed(parts, 2);
+    case GroupFormat.expanded: upper = IpBits.ipv6_groups_uncompressed(parts);
+    }
+    if (!upper.hasSuffix("::")) {
+      upper += ":";
+    }
+    return upper + IpBits.ipv4_as_compressed(IpBits.v4(), host_address & UInt128.mask(32));
+  }
   
 }
 
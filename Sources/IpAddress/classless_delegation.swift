@@ -0,0 +1,79 @@
+//  The character between the network and the prefix length in
+//  the name of an RFC 2317 child zone, "0/26" or "0-26".
+public enum ClasslessSeparator: String {
+  case slash = "/"
+  case dash = "-"
+}
+
+//  A CNAME record of a parent reverse zone pointing into a
+//  classless child zone, +name+ is the owner relative to the
+//  origin of the parent zone.
+public struct CnameRecord: Equatable {
+  public let name: String;
+  public let address: IPAddress;
+  public let target: String;
+}
+
+//  Classless in-addr.arpa delegation (RFC 2317) of IPv4
+//  networks between /25 and /31. The parent /24 zone holds a
+//  CNAME for each address of the network pointing into the
+//  child zone, which carries the PTR records:
+//
+//    let net = IPAddress("192.0.2.64/26")!
+//
+//    net.dns_classless_reverse()
+//      // => "64/26.2.0.192.in-addr.arpa"
+//    net.dns_classless_reverse(ClasslessSeparator.dash)
+//      // => "64-26.2.0.192.in-addr.arpa"
+//    net.dns_classless_cnames()![1].target
+//      // => "65.64/26.2.0.192.in-addr.arpa.", owner "65"
+//    IPAddress.from_dns_reverse("64/26.2.0.192.in-addr.arpa").to_string()
+//      // => "192.0.2.64/26"
+//
+extension IPAddress {
+  func is_classless() -> Bool {
+    return self.is_ipv4() && self.prefix.num > 24 && self.prefix.num < 32;
+  }
+
+  //  The name of the RFC 2317 child zone of the network, nil
+  //  for IPv6 and for IPv4 prefixes outside of /25 to /31.
+  public func dns_classless_reverse(_ separator: ClasslessSeparator = ClasslessSeparator.slash) -> String? {
+    if (!self.is_classless()) {
+      return nil;
+    }
+    let net = self.network();
+    let parent = net.from(net.address, self.prefix.from(24)!).dns_reverse();
+    return "\(net.address.lo & 0xff)\(separator.rawValue)\(self.prefix.num).\(parent)";
+  }
+
+  //  The CNAME records for every address of the network which
+  //  the parent /24 zone needs, nil where dns_classless_reverse
+  //  is nil.
+  public func dns_classless_cnames(_ separator: ClasslessSeparator = ClasslessSeparator.slash) -> [CnameRecord]? {
+    let child = self.dns_classless_reverse(separator);
+    if (child == nil) {
+      return nil;
+    }
+    var ret = [CnameRecord]();
+    for ip in self.with_zone(nil).addresses {
+      let octet = ip.address.lo & 0xff;
+      ret.append(CnameRecord(name: "\(octet)", address: ip.from(ip.address, ip.prefix.from(32)!),
+                             target: "\(octet).\(child!)."));
+    }
+    return ret;
+  }
+
+  //  The RFC 2317 child zone with the PTR records of the
+  //  +hosts+ inside the network, named by the last octet.
+  //  nil where dns_classless_reverse is nil.
+  public func classless_reverse_zone(_ hosts: [IPAddress: String],
+                                     _ separator: ClasslessSeparator = ClasslessSeparator.slash) -> ReverseZone? {
+    let child = self.dns_classless_reverse(separator);
+    if (child == nil) {
+      return nil;
+    }
+    let zone = self.network().with_zone(nil).reverse_zones(hosts).flatMap({ $0.records });
+    let records = zone.map({ PtrRecord(name: "\($0.address.address.lo & 0xff)", address: $0.address, host: $0.host) });
+    return ReverseZone(network: self.network().with_zone(nil), origin: "\(child!).", records: records);
+  }
+}
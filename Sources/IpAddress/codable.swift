@@ -0,0 +1,193 @@
+import BigInt
+
+//  Selects how IPAddress and Prefix are encoded and decoded,
+//  set it in the userInfo of the encoder and the decoder:
+//
+//    let encoder = JSONEncoder()
+//    encoder.userInfo[IPAddressCoding.user_info_key] = IPAddressCoding.object
+//
+//  Without it the cidr representation is used. The decoder only
+//  accepts the selected representation, a mismatch or an invalid
+//  address is a DecodingError.dataCorrupted which wraps the
+//  IPAddressParseError when there is one.
+//
+//                   IPAddress("10.0.0.1/24")                Prefix32.create(24)
+//    cidr           "10.0.0.1/24"                           "255.255.255.0"
+//    address        "10.0.0.1", the prefix is lost          "255.255.255.0"
+//    integer        167772161, the prefix is lost           4294967040
+//    object         {"address":"10.0.0.1","prefix":24}      {"version":4,"prefix":24}
+//
+//  IPv6 integers are written as decimal strings, JSON numbers
+//  can't hold 128 bits. Both integer forms have no room for the
+//  zone, encoding a zoned address throws.
+//
+public enum IPAddressCoding: String {
+  case cidr
+  case address
+  case integer
+  case object
+
+  public static let user_info_key = CodingUserInfoKey(rawValue: "IPAddressCoding")!;
+
+  static func from(_ user_info: [CodingUserInfoKey: Any]) -> IPAddressCoding {
+    return (user_info[IPAddressCoding.user_info_key] as? IPAddressCoding) ?? IPAddressCoding.cidr;
+  }
+}
+
+enum IPAddressCodingKeys: String, CodingKey {
+  case address
+  case prefix
+  case version
+}
+
+extension IPAddress: Codable {
+  public init(from decoder: Decoder) throws {
+    switch (IPAddressCoding.from(decoder.userInfo)) {
+    case IPAddressCoding.cidr:
+      let container = try decoder.singleValueContainer();
+      self = try IPAddress.decode_parse(try container.decode(String.self), container.codingPath);
+    case IPAddressCoding.address:
+      let container = try decoder.singleValueContainer();
+      self = try IPAddress.decode_address(try container.decode(String.self), container.codingPath);
+    case IPAddressCoding.integer:
+      let container = try decoder.singleValueContainer();
+      self = try IPAddress.decode_integer(container);
+    case IPAddressCoding.object:
+      let container = try decoder.container(keyedBy: IPAddressCodingKeys.self);
+      let ip = try IPAddress.decode_address(try container.decode(String.self, forKey: IPAddressCodingKeys.address),
+                                            container.codingPath + [IPAddressCodingKeys.address]);
+      let num = try container.decode(UInt8.self, forKey: IPAddressCodingKeys.prefix);
+      let ret = ip.change_prefix(num);
+      if (ret == nil) {
+        throw DecodingError.dataCorruptedError(forKey: IPAddressCodingKeys.prefix, in: container,
+                                               debugDescription: "prefix out of range '\(num)'");
+      }
+      self = ret!;
+    }
+  }
+
+  public func encode(to encoder: Encoder) throws {
+    switch (IPAddressCoding.from(encoder.userInfo)) {
+    case IPAddressCoding.cidr:
+      var container = encoder.singleValueContainer();
+      try container.encode(self.to_string());
+    case IPAddressCoding.address:
+      var container = encoder.singleValueContainer();
+      try container.encode(self.to_s());
+    case IPAddressCoding.integer:
+      if (self.zone != nil) {
+        throw EncodingError.invalidValue(self, EncodingError.Context(codingPath: encoder.codingPath,
+          debugDescription: "the integer representation has no zone '\(self.zone!)'"));
+      }
+      var container = encoder.singleValueContainer();
+      try IPAddress.encode_integer(self.ip_bits, self.address, &container);
+    case IPAddressCoding.object:
+      var container = encoder.container(keyedBy: IPAddressCodingKeys.self);
+      try container.encode(self.to_s(), forKey: IPAddressCodingKeys.address);
+      try container.encode(self.prefix.num, forKey: IPAddressCodingKeys.prefix);
+    }
+  }
+
+  static func decode_parse(_ str: String, _ path: [CodingKey]) throws -> IPAddress {
+    do {
+      return try IPAddress.parse(str);
+    } catch let err as IPAddressParseError {
+      throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: path,
+        debugDescription: err.description, underlyingError: err));
+    }
+  }
+
+  static func decode_address(_ str: String, _ path: [CodingKey]) throws -> IPAddress {
+    if (str.index(of: "/") != nil) {
+      throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: path,
+        debugDescription: "address with prefix '\(str)'"));
+    }
+    return try IPAddress.decode_parse(str, path);
+  }
+
+  //  IPv4 as number, IPv6 as decimal string
+  static func decode_integer(_ container: SingleValueDecodingContainer) throws -> IPAddress {
+    let num = try? container.decode(UInt32.self);
+    if (num != nil) {
+      return Ipv4.from_u32(num!, 32)!;
+    }
+    let str = try? container.decode(String.self);
+    if (str != nil) {
+      let big = BigUInt(str!, radix: 10);
+      if (big != nil && !str!.isEmpty && str!.allSatisfy({ $0.isASCII && $0.isNumber })) {
+        let ip = Ipv6.from_int(big!, 128);
+        if (ip != nil) {
+          return ip!;
+        }
+      }
+    }
+    throw DecodingError.dataCorruptedError(in: container,
+      debugDescription: "neither an IPv4 number nor an IPv6 decimal string");
+  }
+
+  static func encode_integer(_ ip_bits: IpBits, _ num: UInt128,
+                            _ container: inout SingleValueEncodingContainer) throws {
+    switch (ip_bits.version) {
+    case IpVersion.V4: try container.encode(num.u32);
+    case IpVersion.V6: try container.encode(num.to_string(10));
+    }
+  }
+}
+
+extension Prefix: Codable {
+  public init(from decoder: Decoder) throws {
+    switch (IPAddressCoding.from(decoder.userInfo)) {
+    case IPAddressCoding.cidr, IPAddressCoding.address:
+      let container = try decoder.singleValueContainer();
+      let str = try container.decode(String.self);
+      let prefix = Prefix(str);
+      if (prefix == nil) {
+        throw DecodingError.dataCorruptedError(in: container,
+          debugDescription: "not a contiguous netmask '\(str)'");
+      }
+      self = prefix!;
+    case IPAddressCoding.integer:
+      let container = try decoder.singleValueContainer();
+      let ip = try IPAddress.decode_integer(container);
+      let num = IPAddress.netmask_to_prefix(ip.address, ip.ip_bits.bits);
+      if (num == nil) {
+        throw DecodingError.dataCorruptedError(in: container,
+          debugDescription: "not a contiguous netmask '\(ip.to_s())'");
+      }
+      self = ip.prefix.from(num!)!;
+    case IPAddressCoding.object:
+      let container = try decoder.container(keyedBy: IPAddressCodingKeys.self);
+      let version = try container.decode(UInt8.self, forKey: IPAddressCodingKeys.version);
+      let num = try container.decode(UInt8.self, forKey: IPAddressCodingKeys.prefix);
+      var prefix: Prefix?;
+      switch (version) {
+      case 4: prefix = Prefix32.create(num);
+      case 6: prefix = Prefix128.create(num);
+      default:
+        throw DecodingError.dataCorruptedError(forKey: IPAddressCodingKeys.version, in: container,
+                                               debugDescription: "version is neither 4 nor 6 '\(version)'");
+      }
+      if (prefix == nil) {
+        throw DecodingError.dataCorruptedError(forKey: IPAddressCodingKeys.prefix, in: container,
+                                               debugDescription: "prefix out of range '\(num)'");
+      }
+      self = prefix!;
+    }
+  }
+
+  public func encode(to encoder: Encoder) throws {
+    switch (IPAddressCoding.from(encoder.userInfo)) {
+    case IPAddressCoding.cidr, IPAddressCoding.address:
+      var container = encoder.singleValueContainer();
+      try container.encode(self.description);
+    case IPAddressCoding.integer:
+      var container = encoder.singleValueContainer();
+      try IPAddress.encode_integer(self.ip_bits, self.net_mask, &container);
+    case IPAddressCoding.object:
+      var container = encoder.container(keyedBy: IPAddressCodingKeys.self);
+      let version: UInt8 = self.ip_bits.version == IpVersion.V4 ? 4 : 6;
+      try container.encode(version, forKey: IPAddressCodingKeys.version);
+      try container.encode(self.num, forKey: IPAddressCodingKeys.prefix);
+    }
+  }
+}
@@ -0,0 +1,84 @@
+//  How IPAddress.to_ipv6(_:) puts an IPv4 address into an IPv6
+//  address. The IPv4 prefix is carried over into the IPv6
+//  prefix, a host stays a host.
+public enum Ipv6Embedding: Equatable {
+  //  ::ffff:a.b.c.d, the prefix plus 96 (RFC 4291)
+  case mapped
+  //  ::a.b.c.d, the prefix plus 96, deprecated by RFC 4291
+  case compatible
+  //  2002:<ipv4>::/48 of RFC 3056, the prefix plus 16, a host
+  //  gives its /48 site prefix
+  case six_to_four
+  //  Behind a NAT64 prefix as in RFC 6052, see
+  //  IPAddress.to_nat64
+  case nat64(IPAddress)
+}
+
+//  Explicit conversions between IPv4 and IPv6 which translate
+//  the prefix as well:
+//
+//    let ip = IPAddress("192.0.2.0/24")!
+//
+//    ip.to_ipv6(Ipv6Embedding.mapped)!.to_string()
+//      // => "::ffff:c000:200/120"
+//    ip.to_ipv6(Ipv6Embedding.six_to_four)!.to_string()
+//      // => "2002:c000:200::/40"
+//    ip.to_ipv6(Ipv6Embedding.nat64(IPAddress("2001:db8::/32")!))!.to_string()
+//      // => "2001:db8:c000:200::/56"
+//    IPAddress("2002:c000:200::/40")!.to_ipv4()!.to_string()
+//      // => "192.0.2.0/24"
+//
+extension IPAddress {
+  //  The IPv6 address of an IPv4 address in the given
+  //  embedding, nil where the embedding cannot hold it (a NAT64
+  //  prefix of the wrong length). IPv6 addresses are returned
+  //  as they are.
+  public func to_ipv6(_ embedding: Ipv6Embedding) -> IPAddress? {
+    if (self.is_ipv6()) {
+      return self;
+    }
+    switch (embedding) {
+    case Ipv6Embedding.mapped:
+      return Ipv6.from_u128(UInt128(UInt64(0xffff)) << 32 | UInt128(self.address.u32), self.prefix.num + 96);
+    case Ipv6Embedding.compatible:
+      return Ipv6.from_u128(UInt128(self.address.u32), self.prefix.num + 96);
+    case Ipv6Embedding.six_to_four:
+      return self.to_6to4();
+    case Ipv6Embedding.nat64(let prefix):
+      return self.to_nat64(prefix);
+    }
+  }
+
+  //  True for ::a.b.c.d, except for :: and ::1
+  public func is_ipv4_compatible() -> Bool {
+    return self.is_ipv6() && (self.address >> 32).is_zero && self.address.u32 > 1;
+  }
+
+  //  The IPv4 address wrapped in this address, the opposite of
+  //  to_ipv6(_:): IPv4 mapped, IPv4 compatible, 6to4 or NAT64
+  //  behind +nat64_prefix+. The IPv6 prefix is translated back,
+  //  nil if the address wraps no IPv4 address or its prefix
+  //  ends in front of the IPv4 address. IPv4 addresses are
+  //  returned as they are.
+  //
+  //    IPAddress("::ffff:192.0.2.1")!.to_ipv4()!.to_string()
+  //      // => "192.0.2.1/32"
+  //    IPAddress("64:ff9b::c000:200/120")!.to_ipv4()!.to_string()
+  //      // => "192.0.2.0/24"
+  //
+  public func to_ipv4(_ nat64_prefix: IPAddress = IPAddress.nat64_well_known_prefix) -> IPAddress? {
+    if (self.is_ipv4()) {
+      return self;
+    }
+    if ((self.address >> 32) == UInt128(0xffff) || self.is_ipv4_compatible()) {
+      if (self.prefix.num < 96) {
+        return nil;
+      }
+      return Ipv4.from_u32(self.address.u32, self.prefix.num - 96);
+    }
+    if (self.is_6to4()) {
+      return self.six_to_four_ipv4();
+    }
+    return self.nat64_ipv4(nat64_prefix);
+  }
+}
@@ -0,0 +1,223 @@
+//  A MAC address, an IEEE EUI-48 identifier in the low 48 bits
+//  of value. It parses the colon, dash, Cisco dotted and bare
+//  hex notations:
+//
+//    let mac = try! Eui48.parse("00-1A-2B-3C-4D-5E")
+//
+//    mac.to_s()
+//      // => "00:1a:2b:3c:4d:5e"
+//    mac.to_s_cisco()
+//      // => "001a.2b3c.4d5e"
+//    Ipv6.from_prefix(IPAddress("2001:db8::/64")!, mac: mac)!.to_string()
+//      // => "2001:db8::21a:2bff:fe3c:4d5e/64"
+//
+public struct Eui48: Hashable, Comparable, CustomStringConvertible, LosslessStringConvertible {
+  public let value: UInt64;
+
+  //  nil if +value+ is wider than 48 bits
+  public init?(_ value: UInt64) {
+    if (value >> 48 != 0) {
+      return nil;
+    }
+    self.value = value;
+  }
+
+  public init?(_ description: String) {
+    let mac = try? Eui48.parse(description);
+    if (mac == nil) {
+      return nil;
+    }
+    self = mac!;
+  }
+
+  public static func parse(_ str: String) throws -> Eui48 {
+    return Eui48(try Eui48.parse_bytes(str, 6))!;
+  }
+
+  //  Parses +bytes+ bytes in one of the notations:
+  //  "00:1a:2b:3c:4d:5e" (single digits allowed), "00-1a-2b-3c-4d-5e",
+  //  "001a.2b3c.4d5e" or "001a2b3c4d5e".
+  static func parse_bytes(_ str: String, _ bytes: Int) throws -> UInt64 {
+    var separator: Character? = nil;
+    for sep in [":", "-", "."] as [Character] {
+      if (str.contains(sep)) {
+        separator = sep;
+        break;
+      }
+    }
+    var width = str.count;
+    var groups = [str];
+    if (separator != nil) {
+      width = separator! == "." ? 4 : 2;
+      groups = str.split(separator: separator!, omittingEmptySubsequences: false).map({ String($0) });
+    }
+    if (groups.count * width != 2 * bytes) {
+      throw IPAddressParseError.wrong_eui_length(str, 0);
+    }
+    var ret: UInt64 = 0;
+    var ofs = 0;
+    for group in groups {
+      let single = separator == ":" && group.count == 1;
+      if ((group.count != width && !single) || !group.allSatisfy({ $0.isASCII && $0.isHexDigit })) {
+        throw IPAddressParseError.invalid_eui_group(group, ofs);
+      }
+      ret = ret << UInt64(4 * width) | UInt64(group, radix: 16)!;
+      ofs += group.count + 1;
+    }
+    return ret;
+  }
+
+  //  The groups of +width+ hex digits of the lowest +bytes+
+  //  bytes of +value+ joined by +separator+
+  static func to_hex(_ value: UInt64, _ bytes: Int, _ width: Int, _ separator: String) -> String {
+    var ret = [String]();
+    for i in stride(from: 2 * bytes - width, through: 0, by: -width) {
+      let group = (value >> UInt64(4 * i)) & ((1 << UInt64(4 * width)) - 1);
+      let hex = String(group | (1 << UInt64(4 * width)), radix: 16);
+      ret.append(String(hex.dropFirst(1)));
+    }
+    return ret.joined(separator: separator);
+  }
+
+  public var description: String {
+    return self.to_s();
+  }
+
+  public func to_s() -> String {
+    return Eui48.to_hex(self.value, 6, 2, ":");
+  }
+
+  public func to_s_dashed() -> String {
+    return Eui48.to_hex(self.value, 6, 2, "-");
+  }
+
+  public func to_s_cisco() -> String {
+    return Eui48.to_hex(self.value, 6, 4, ".");
+  }
+
+  //  The I/G bit, set for group addresses
+  public func is_multicast() -> Bool {
+    return self.value & 0x010000000000 != 0;
+  }
+
+  //  The U/L bit, set for locally administered addresses
+  public func is_local() -> Bool {
+    return self.value & 0x020000000000 != 0;
+  }
+
+  //  The EUI-64 of the MAC, ff:fe inserted in the middle
+  //
+  //    Eui48("00:1a:2b:3c:4d:5e")!.to_eui64().to_s()
+  //      // => "00:1a:2b:ff:fe:3c:4d:5e"
+  //
+  public func to_eui64() -> Eui64 {
+    return Eui64((self.value >> 24) << 40 | 0xfffe << 24 | self.value & 0xffffff);
+  }
+
+  //  The modified EUI-64 interface identifier of RFC 4291,
+  //  the EUI-64 with the U/L bit flipped
+  public func interface_id() -> UInt64 {
+    return self.to_eui64().interface_id();
+  }
+
+  public static func <(lhs: Eui48, rhs: Eui48) -> Bool {
+    return lhs.value < rhs.value;
+  }
+}
+
+//  An IEEE EUI-64 identifier, in the notations of Eui48 with
+//  eight bytes.
+public struct Eui64: Hashable, Comparable, CustomStringConvertible, LosslessStringConvertible {
+  public let value: UInt64;
+
+  public init(_ value: UInt64) {
+    self.value = value;
+  }
+
+  public init?(_ description: String) {
+    let eui = try? Eui64.parse(description);
+    if (eui == nil) {
+      return nil;
+    }
+    self = eui!;
+  }
+
+  public static func parse(_ str: String) throws -> Eui64 {
+    return Eui64(try Eui48.parse_bytes(str, 8));
+  }
+
+  //  The EUI-64 of a modified EUI-64 interface identifier
+  public static func from_interface_id(_ iid: UInt64) -> Eui64 {
+    return Eui64(iid ^ 0x0200000000000000);
+  }
+
+  public var description: String {
+    return self.to_s();
+  }
+
+  public func to_s() -> String {
+    return Eui48.to_hex(self.value, 8, 2, ":");
+  }
+
+  public func to_s_dashed() -> String {
+    return Eui48.to_hex(self.value, 8, 2, "-");
+  }
+
+  public func to_s_cisco() -> String {
+    return Eui48.to_hex(self.value, 8, 4, ".");
+  }
+
+  //  The modified EUI-64 interface identifier of RFC 4291,
+  //  the U/L bit flipped
+  public func interface_id() -> UInt64 {
+    return self.value ^ 0x0200000000000000;
+  }
+
+  //  The MAC of an EUI-64 built from one, nil without the
+  //  ff:fe in the middle
+  public func to_eui48() -> Eui48? {
+    if ((self.value >> 24) & 0xffff != 0xfffe) {
+      return nil;
+    }
+    return Eui48((self.value >> 40) << 24 | self.value & 0xffffff);
+  }
+
+  public static func <(lhs: Eui64, rhs: Eui64) -> Bool {
+    return lhs.value < rhs.value;
+  }
+}
+
+//  The interface identifier of IPv6 addresses, the SLAAC
+//  direction is Ipv6.from_prefix:
+//
+//    let ip = IPAddress("fe80::21a:2bff:fe3c:4d5e%eth0")!
+//
+//    ip.mac()!.to_s()
+//      // => "00:1a:2b:3c:4d:5e"
+//    ip.eui64()!.to_s()
+//      // => "00:1a:2b:ff:fe:3c:4d:5e"
+//
+extension IPAddress {
+  //  The last 64 bits of an IPv6 address
+  public func interface_id() -> UInt64? {
+    if (!self.is_ipv6()) {
+      return nil;
+    }
+    return self.address.lo;
+  }
+
+  //  The EUI-64 of a modified EUI-64 interface identifier
+  public func eui64() -> Eui64? {
+    let iid = self.interface_id();
+    if (iid == nil) {
+      return nil;
+    }
+    return Eui64.from_interface_id(iid!);
+  }
+
+  //  The MAC of an interface identifier built from one, nil
+  //  without the ff:fe pattern in the middle
+  public func mac() -> Eui48? {
+    return self.eui64()?.to_eui48();
+  }
+}
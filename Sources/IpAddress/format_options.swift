@@ -0,0 +1,56 @@
+//  How the groups of an IPv6 address are written, IPv4
+//  addresses look the same in every format.
+public enum GroupFormat {
+  //  "::" for the first longest run of zero groups, this is
+  //  to_s and to_string
+  case compressed
+  //  RFC 5952, like compressed but a single zero group is
+  //  never replaced by "::"
+  case canonical
+  //  Eight groups of four hex digits, this is to_s_uncompressed
+  case expanded
+}
+
+//  How the prefix is appended to the address
+public enum PrefixFormat {
+  case none
+  //  "/24"
+  case length
+  //  "/255.255.255.0", for IPv6 the netmask is written in
+  //  the group format of the address, "/ffff:ffff:ffff:ffff::"
+  case netmask
+}
+
+//  Options for IPAddress.format, the defaults give the same
+//  text as to_string and will not change:
+//
+//    ip6 = IPAddress("::ffff:c000:201/120")
+//
+//    ip6.format()
+//      // => "::ffff:c000:201/120"
+//    ip6.format(FormatOptions(groups: GroupFormat.expanded, uppercase: true))
+//      // => "0000:0000:0000:0000:0000:FFFF:C000:0201/120"
+//    ip6.format(FormatOptions(embedded_ipv4: true, prefix: PrefixFormat.none))
+//      // => "::ffff:192.0.2.1"
+//
+public struct FormatOptions: Equatable {
+  public let groups: GroupFormat;
+  //  Upper case hex digits, the zone is kept as it is
+  public let uppercase: Bool;
+  //  The last 32 bits of an IPv6 address as dotted IPv4
+  //  address, as in "::ffff:192.0.2.1" or "64:ff9b::192.0.2.1"
+  public let embedded_ipv4: Bool;
+  public let prefix: PrefixFormat;
+
+  public init(groups: GroupFormat = GroupFormat.compressed,
+              uppercase: Bool = false,
+              embedded_ipv4: Bool = false,
+              prefix: PrefixFormat = PrefixFormat.length) {
+    self.groups = groups;
+    self.uppercase = uppercase;
+    self.embedded_ipv4 = embedded_ipv4;
+    self.prefix = prefix;
+  }
+
+  public static let standard = FormatOptions();
+}
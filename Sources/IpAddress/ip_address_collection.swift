@@ -0,0 +1,141 @@
+import BigInt
+
+//  A lazy view of consecutive addresses or networks of one size,
+//  returned by IPAddress.hosts, IPAddress.addresses and
+//  IPAddress.subnets. Nothing is allocated up front, every
+//  element is built when it is read:
+//
+//    let net = IPAddress("10.0.0.0/24")!
+//
+//    net.hosts[5].to_s()
+//      // => "10.0.0.6"
+//    net.hosts[-1].to_s()
+//      // => "10.0.0.254"
+//    net.addresses.reversed().prefix(2).map({ $0.to_s() })
+//      // => ["10.0.0.255", "10.0.0.254"]
+//    net.subnets(26)![1].to_string()
+//      // => "10.0.0.64/26"
+//
+//  The elements keep the zone of the network. Indexes are 128
+//  bit numbers, so walking the /64s of a /0 works, only count
+//  and distance(from:to:) trap when the result does not fit an
+//  Int; use size() for the number of elements of large views.
+//
+public struct IPAddressCollection: RandomAccessCollection {
+  public struct Index: Comparable {
+    let offset: UInt128;
+    let past_end: Bool;
+
+    public static func ==(lhs: Index, rhs: Index) -> Bool {
+      return lhs.past_end == rhs.past_end && lhs.offset == rhs.offset;
+    }
+
+    public static func <(lhs: Index, rhs: Index) -> Bool {
+      if (lhs.past_end != rhs.past_end) {
+        return rhs.past_end;
+      }
+      return lhs.offset < rhs.offset;
+    }
+  }
+
+  let network: IPAddress;
+  let first: UInt128;
+  let prefix: Prefix;
+  let shift: Int;
+  let last_offset: UInt128;
+  let empty: Bool;
+
+  //  The elements of +prefix+ from the address +first+, the
+  //  element at offset n is +first+ + (n << +shift+) and the
+  //  last one is at +last_offset+. None if +empty+.
+  init(_ network: IPAddress, _ first: UInt128, _ prefix: Prefix, _ shift: Int,
+       _ last_offset: UInt128, _ empty: Bool) {
+    self.network = network;
+    self.first = first;
+    self.prefix = prefix;
+    self.shift = shift;
+    self.last_offset = last_offset;
+    self.empty = empty;
+  }
+
+  public var startIndex: Index {
+    return self.empty ? self.endIndex : Index(offset: UInt128.zero, past_end: false);
+  }
+
+  public var endIndex: Index {
+    return Index(offset: self.last_offset, past_end: true);
+  }
+
+  //  The number of elements, 2**128 for all the addresses
+  //  of ::/0
+  public func size() -> BigUInt {
+    return self.empty ? BigUInt(0) : self.last_offset.big + BigUInt(1);
+  }
+
+  public subscript(position: Index) -> IPAddress {
+    precondition(!position.past_end && !self.empty, "IPAddressCollection index out of range");
+    return self.network.from(self.first + (position.offset << self.shift), self.prefix);
+  }
+
+  //  The element at +offset+ from the start, a negative
+  //  +offset+ counts from the end, -1 is the last one.
+  public subscript(offset: Int) -> IPAddress {
+    let base = offset < 0 ? self.endIndex : self.startIndex;
+    return self[self.index(base, offsetBy: offset)];
+  }
+
+  //  The element at +offset+, nil if there are not so many
+  public func at(_ offset: UInt128) -> IPAddress? {
+    if (self.empty || offset > self.last_offset) {
+      return nil;
+    }
+    return self[Index(offset: offset, past_end: false)];
+  }
+
+  public func index(after i: Index) -> Index {
+    return self.index(i, offsetBy: 1);
+  }
+
+  public func index(before i: Index) -> Index {
+    return self.index(i, offsetBy: -1);
+  }
+
+  public func index(_ i: Index, offsetBy n: Int) -> Index {
+    let steps = UInt128(UInt64(n.magnitude));
+    if (n >= 0) {
+      precondition(!i.past_end || n == 0, "IPAddressCollection index out of range");
+      if (i.past_end || n == 0) {
+        return i;
+      }
+      let left = self.last_offset - i.offset;
+      if (steps - UInt128.one == left) {
+        return self.endIndex;
+      }
+      precondition(steps <= left, "IPAddressCollection index out of range");
+      return Index(offset: i.offset + steps, past_end: false);
+    }
+    // one step back from the end is the last element
+    let from = i.past_end ? self.last_offset : i.offset;
+    let back = i.past_end ? steps - UInt128.one : steps;
+    precondition(!self.empty && back <= from, "IPAddressCollection index out of range");
+    return Index(offset: from - back, past_end: false);
+  }
+
+  public func distance(from start: Index, to end: Index) -> Int {
+    if (end < start) {
+      return -self.distance(from: end, to: start);
+    }
+    if (start == end) {
+      return 0;
+    }
+    if (!end.past_end) {
+      return IPAddressCollection.to_int(end.offset - start.offset);
+    }
+    return IPAddressCollection.to_int(self.last_offset - start.offset) + 1;
+  }
+
+  static func to_int(_ num: UInt128) -> Int {
+    precondition(num.hi == 0 && num.lo <= UInt64(Int.max), "IPAddressCollection distance does not fit an Int");
+    return Int(num.lo);
+  }
+}
@@ -0,0 +1,183 @@
+import BigInt
+
+//  A set of addresses of both families with set algebra. It is
+//  kept as sorted disjoint ranges, so the operations cost the
+//  number of ranges and not the number of addresses, and
+//  networks() always returns the minimal CIDR form:
+//
+//    let bogons = IPAddressSet(IPAddress.to_ipaddress_vec(
+//      ["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"])!)
+//
+//    IPAddress.to_string_vec(bogons.complement(IPAddress("0.0.0.0/0")!).networks())
+//      // => ["0.0.0.0/5", "8.0.0.0/7", "11.0.0.0/8", "12.0.0.0/6", ...]
+//
+//  The zones of the networks are ignored.
+//
+public struct IPAddressSet: Equatable, CustomStringConvertible {
+  //  IPv4 ranges first, then IPv6, each in ascending order,
+  //  neither overlapping nor adjacent
+  public let ranges: [IPRange];
+
+  public init() {
+    self.ranges = [];
+  }
+
+  public init(_ networks: [IPAddress]) {
+    self.ranges = IPAddressSet.normalize(networks.map({ IPRange($0) }));
+  }
+
+  public init(_ ranges: [IPRange]) {
+    self.ranges = IPAddressSet.normalize(ranges);
+  }
+
+  init(normalized: [IPRange]) {
+    self.ranges = normalized;
+  }
+
+  public var description: String {
+    return IPAddress.to_string_vec(self.networks()).joined(separator: ", ");
+  }
+
+  public var is_empty: Bool {
+    return self.ranges.isEmpty;
+  }
+
+  //  The minimal list of networks which covers exactly
+  //  the set, in ascending order.
+  public func networks() -> [IPAddress] {
+    var ret = [IPAddress]();
+    for range in self.ranges {
+      ret.append(contentsOf: range.networks());
+    }
+    return ret;
+  }
+
+  //  The number of addresses in the set
+  public func size() -> BigUInt {
+    var ret = BigUInt(0);
+    for range in self.ranges {
+      ret += range.size();
+    }
+    return ret;
+  }
+
+  //  True if the whole network of +ip+ is in the set
+  public func includes(_ ip: IPAddress) -> Bool {
+    let net = ip.network();
+    // the last range which starts at or before the network
+    var low = 0;
+    var high = self.ranges.count;
+    while (low < high) {
+      let mid = (low + high) / 2;
+      if (IPAddressSet.starts_before(self.ranges[mid], net)) {
+        low = mid + 1;
+      } else {
+        high = mid;
+      }
+    }
+    return low > 0 && self.ranges[low - 1].includes(ip);
+  }
+
+  public func union(_ oth: IPAddressSet) -> IPAddressSet {
+    return IPAddressSet(self.ranges + oth.ranges);
+  }
+
+  public func intersection(_ oth: IPAddressSet) -> IPAddressSet {
+    var ret = [IPRange]();
+    var i = 0;
+    var j = 0;
+    while (i < self.ranges.count && j < oth.ranges.count) {
+      let a = self.ranges[i];
+      let b = oth.ranges[j];
+      if (a.overlaps(b)) {
+        ret.append(IPRange(a.ip_bits, max(a.first.address, b.first.address),
+                           min(a.last.address, b.last.address)));
+      }
+      // drop the range which ends first
+      if (IPAddressSet.ends_before(a, b)) {
+        i += 1;
+      } else {
+        j += 1;
+      }
+    }
+    return IPAddressSet(normalized: ret);
+  }
+
+  public func subtracting(_ oth: IPAddressSet) -> IPAddressSet {
+    var ret = [IPRange]();
+    var j = 0;
+    for range in self.ranges {
+      var cur: IPRange? = range;
+      // skip the ranges of oth which end before this one
+      while (j < oth.ranges.count && IPAddressSet.ends_before(oth.ranges[j], range) &&
+        !oth.ranges[j].overlaps(range)) {
+        j += 1;
+      }
+      var k = j;
+      while (cur != nil && k < oth.ranges.count && oth.ranges[k].overlaps(cur!)) {
+        let hole = oth.ranges[k];
+        let c = cur!;
+        if (c.first.address < hole.first.address) {
+          ret.append(IPRange(c.ip_bits, c.first.address, hole.first.address - UInt128.one));
+        }
+        cur = nil;
+        if (hole.last.address < c.last.address) {
+          cur = IPRange(c.ip_bits, hole.last.address + UInt128.one, c.last.address);
+        }
+        k += 1;
+      }
+      if (cur != nil) {
+        ret.append(cur!);
+      }
+    }
+    return IPAddressSet(normalized: ret);
+  }
+
+  public func symmetric_difference(_ oth: IPAddressSet) -> IPAddressSet {
+    return self.union(oth).subtracting(self.intersection(oth));
+  }
+
+  //  The addresses of the network +universe+ which are not
+  //  in the set, pass 0.0.0.0/0 or ::/0 for the complement
+  //  within a family.
+  public func complement(_ universe: IPAddress) -> IPAddressSet {
+    return IPAddressSet([universe]).subtracting(self);
+  }
+
+  //  True if +range+ starts at or before +ip+
+  static func starts_before(_ range: IPRange, _ ip: IPAddress) -> Bool {
+    if (!range.first.is_same_kind(ip)) {
+      return range.first.is_ipv4();
+    }
+    return range.first.address <= ip.address;
+  }
+
+  //  True if +a+ ends before +b+ ends
+  static func ends_before(_ a: IPRange, _ b: IPRange) -> Bool {
+    if (!a.first.is_same_kind(b.first)) {
+      return a.first.is_ipv4();
+    }
+    return a.last.address < b.last.address;
+  }
+
+  //  Sorts +ranges+ and merges the overlapping and
+  //  adjacent ones.
+  static func normalize(_ ranges: [IPRange]) -> [IPRange] {
+    let sorted = ranges.sorted(by: { $0.first.lt($1.first) });
+    var ret = [IPRange]();
+    for range in sorted {
+      if (!ret.isEmpty) {
+        let prev = ret[ret.count - 1];
+        let (after_prev, overflow) = prev.last.address.addingReportingOverflow(UInt128.one);
+        if (prev.first.is_same_kind(range.first) && (overflow || range.first.address <= after_prev)) {
+          if (range.last.address > prev.last.address) {
+            ret[ret.count - 1] = IPRange(prev.ip_bits, prev.first.address, range.last.address);
+          }
+          continue;
+        }
+      }
+      ret.append(range);
+    }
+    return ret;
+  }
+}
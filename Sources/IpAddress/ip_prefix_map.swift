@@ -0,0 +1,295 @@
+//  A map from networks to values with longest prefix match,
+//  stored in one path compressed binary trie (Patricia trie)
+//  per family, IPv4 keys use the 32 bits and IPv6 keys the 128
+//  bits of their IpBits.
+//
+//    var acl = IPPrefixMap<String>()
+//    acl[IPAddress("10.0.0.0/8")!] = "deny"
+//    acl[IPAddress("10.1.0.0/16")!] = "allow"
+//
+//    acl.longest_match(IPAddress("10.1.2.3")!)!.value
+//      // => "allow"
+//    acl.covering(IPAddress("10.1.2.3")!).map({ $0.key.to_string() })
+//      // => ["10.0.0.0/8", "10.1.0.0/16"]
+//
+//  A key is stored as its network, 10.1.2.3/8 is the key
+//  10.0.0.0/8, and its zone is ignored. Lookups, insert and
+//  remove walk at most one node per bit of the family.
+//  Iteration is ordered like IPAddress.cmp: IPv4 before IPv6,
+//  then by address and shorter prefixes first.
+//
+//  IPPrefixMap is a value type like IPAddress, a copy shares
+//  the nodes until one of them is changed.
+public struct IPPrefixMap<Value>: Sequence {
+  public typealias Element = (key: IPAddress, value: Value);
+
+  final class Node {
+    let addr: UInt128;
+    let len: UInt8;
+    var entry: Element?;
+    var children: [Node?] = [nil, nil];
+
+    init(_ addr: UInt128, _ len: UInt8, _ entry: Element?) {
+      self.addr = addr;
+      self.len = len;
+      self.entry = entry;
+    }
+
+    func copy() -> Node {
+      let ret = Node(self.addr, self.len, self.entry);
+      ret.children = self.children.map({ $0?.copy() });
+      return ret;
+    }
+  }
+
+  final class Storage {
+    var v4: Node?;
+    var v6: Node?;
+    var count = 0;
+
+    func copy() -> Storage {
+      let ret = Storage();
+      ret.v4 = self.v4?.copy();
+      ret.v6 = self.v6?.copy();
+      ret.count = self.count;
+      return ret;
+    }
+  }
+
+  var storage = Storage();
+
+  public init() {
+  }
+
+  public var count: Int {
+    return self.storage.count;
+  }
+
+  public var is_empty: Bool {
+    return self.storage.count == 0;
+  }
+
+  func root(_ ip_bits: IpBits) -> Node? {
+    switch (ip_bits.version) {
+    case IpVersion.V4: return self.storage.v4;
+    case IpVersion.V6: return self.storage.v6;
+    }
+  }
+
+  mutating func set_root(_ ip_bits: IpBits, _ node: Node?) {
+    switch (ip_bits.version) {
+    case IpVersion.V4: self.storage.v4 = node;
+    case IpVersion.V6: self.storage.v6 = node;
+    }
+  }
+
+  mutating func make_unique() {
+    if (!isKnownUniquelyReferenced(&self.storage)) {
+      self.storage = self.storage.copy();
+    }
+  }
+
+  //  Sets the value of the network +key+, returns the value
+  //  it replaced.
+  @discardableResult
+  public mutating func insert(_ key: IPAddress, _ value: Value) -> Value? {
+    self.make_unique();
+    let net = key.network().with_zone(nil);
+    var old: Value? = nil;
+    self.set_root(net.ip_bits, IPPrefixMap.insert(self.root(net.ip_bits), net.address, net.prefix.num,
+                                                  net.ip_bits.bits, (net, value), &old));
+    if (old == nil) {
+      self.storage.count += 1;
+    }
+    return old;
+  }
+
+  //  Removes the network +key+, returns its value.
+  @discardableResult
+  public mutating func remove(_ key: IPAddress) -> Value? {
+    if (self.get(key) == nil) {
+      return nil;
+    }
+    self.make_unique();
+    let net = key.network();
+    var old: Value? = nil;
+    self.set_root(net.ip_bits, IPPrefixMap.remove(self.root(net.ip_bits), net.address, net.prefix.num,
+                                                  net.ip_bits.bits, &old));
+    self.storage.count -= 1;
+    return old;
+  }
+
+  //  The value of exactly the network +key+
+  public func get(_ key: IPAddress) -> Value? {
+    let net = key.network();
+    var node = self.root(net.ip_bits);
+    let bits = net.ip_bits.bits;
+    while (node != nil) {
+      let cur = node!;
+      if (!IPPrefixMap.matches(cur, net.address, net.prefix.num, bits)) {
+        return nil;
+      }
+      if (cur.len == net.prefix.num) {
+        return cur.entry?.value;
+      }
+      node = cur.children[IPPrefixMap.bit(net.address, bits, cur.len)];
+    }
+    return nil;
+  }
+
+  public subscript(key: IPAddress) -> Value? {
+    get {
+      return self.get(key);
+    }
+    set {
+      if (newValue == nil) {
+        self.remove(key);
+      } else {
+        self.insert(key, newValue!);
+      }
+    }
+  }
+
+  //  The most specific network which includes +ip+, for a
+  //  network +ip+ the network itself or one of its supernets.
+  public func longest_match(_ ip: IPAddress) -> Element? {
+    return self.covering(ip).last;
+  }
+
+  //  All the networks which include +ip+, shortest prefix first.
+  public func covering(_ ip: IPAddress) -> [Element] {
+    let net = ip.network();
+    var ret = [Element]();
+    var node = self.root(net.ip_bits);
+    let bits = net.ip_bits.bits;
+    while (node != nil) {
+      let cur = node!;
+      if (!IPPrefixMap.matches(cur, net.address, net.prefix.num, bits)) {
+        break;
+      }
+      if (cur.entry != nil) {
+        ret.append(cur.entry!);
+      }
+      if (cur.len == net.prefix.num) {
+        break;
+      }
+      node = cur.children[IPPrefixMap.bit(net.address, bits, cur.len)];
+    }
+    return ret;
+  }
+
+  //  All the networks included in +ip+, the network itself
+  //  and its subnets, in the order of the iteration.
+  public func covered(_ ip: IPAddress) -> [Element] {
+    let net = ip.network();
+    var ret = [Element]();
+    var node = self.root(net.ip_bits);
+    let bits = net.ip_bits.bits;
+    while (node != nil) {
+      let cur = node!;
+      let len = min(cur.len, net.prefix.num);
+      if (IPPrefixMap.common_len(cur.addr, net.address, bits, len) < len) {
+        break;
+      }
+      if (cur.len >= net.prefix.num) {
+        IPPrefixMap.collect(cur, &ret);
+        break;
+      }
+      node = cur.children[IPPrefixMap.bit(net.address, bits, cur.len)];
+    }
+    return ret;
+  }
+
+  public func makeIterator() -> IndexingIterator<[Element]> {
+    var ret = [Element]();
+    IPPrefixMap.collect(self.storage.v4, &ret);
+    IPPrefixMap.collect(self.storage.v6, &ret);
+    return ret.makeIterator();
+  }
+
+  //  The bit +idx+ of +addr+ counted from the most
+  //  significant bit of the family.
+  static func bit(_ addr: UInt128, _ bits: UInt8, _ idx: UInt8) -> Int {
+    return addr.bit(Int(bits) - 1 - Int(idx)) ? 1 : 0;
+  }
+
+  //  The number of leading bits +a+ and +b+ have in common,
+  //  at most +max+.
+  static func common_len(_ a: UInt128, _ b: UInt128, _ bits: UInt8, _ max: UInt8) -> UInt8 {
+    let diff = (a ^ b) << (128 - Int(bits));
+    return UInt8(Swift.min(diff.leadingZeroBitCount, Int(max)));
+  }
+
+  //  True if the network of +node+ includes +addr+/+len+
+  static func matches(_ node: Node, _ addr: UInt128, _ len: UInt8, _ bits: UInt8) -> Bool {
+    return node.len <= len && IPPrefixMap.common_len(node.addr, addr, bits, node.len) == node.len;
+  }
+
+  static func insert(_ node: Node?, _ addr: UInt128, _ len: UInt8, _ bits: UInt8,
+                     _ entry: Element, _ old: inout Value?) -> Node {
+    if (node == nil) {
+      return Node(addr, len, entry);
+    }
+    let cur = node!;
+    let common = IPPrefixMap.common_len(cur.addr, addr, bits, min(cur.len, len));
+    if (common == cur.len && common == len) {
+      old = cur.entry?.value;
+      cur.entry = entry;
+      return cur;
+    }
+    if (common == cur.len) {
+      let idx = IPPrefixMap.bit(addr, bits, cur.len);
+      cur.children[idx] = IPPrefixMap.insert(cur.children[idx], addr, len, bits, entry, &old);
+      return cur;
+    }
+    var ret: Node;
+    if (common == len) {
+      // the new network is a supernet of cur
+      ret = Node(addr, len, entry);
+    } else {
+      // cur and the new network split after common bits
+      ret = Node(IPAddress.to_network(addr, bits - common), common, nil);
+      ret.children[IPPrefixMap.bit(addr, bits, common)] = Node(addr, len, entry);
+    }
+    ret.children[IPPrefixMap.bit(cur.addr, bits, common)] = cur;
+    return ret;
+  }
+
+  static func remove(_ node: Node?, _ addr: UInt128, _ len: UInt8, _ bits: UInt8,
+                     _ old: inout Value?) -> Node? {
+    if (node == nil) {
+      return nil;
+    }
+    let cur = node!;
+    if (!IPPrefixMap.matches(cur, addr, len, bits)) {
+      return cur;
+    }
+    if (cur.len == len) {
+      old = cur.entry?.value;
+      cur.entry = nil;
+    } else {
+      let idx = IPPrefixMap.bit(addr, bits, cur.len);
+      cur.children[idx] = IPPrefixMap.remove(cur.children[idx], addr, len, bits, &old);
+    }
+    if (cur.entry != nil) {
+      return cur;
+    }
+    // a node without value is only needed to join two subtries
+    let children = cur.children.compactMap({ $0 });
+    if (children.count == 2) {
+      return cur;
+    }
+    return children.first;
+  }
+
+  static func collect(_ node: Node?, _ ret: inout [Element]) {
+    if (node == nil) {
+      return;
+    }
+    if (node!.entry != nil) {
+      ret.append(node!.entry!);
+    }
+    IPPrefixMap.collect(node!.children[0], &ret);
+    IPPrefixMap.collect(node!.children[1], &ret);
+  }
+}
@@ -0,0 +1,171 @@
+import BigInt
+
+//  An arbitrary range of addresses of one family, from the
+//  address first to the address last, both included. Unlike a
+//  network it needs not start or end on a prefix boundary:
+//
+//    range = IPRange.parse("10.0.0.5 - 10.0.1.20")
+//
+//    range.size()
+//      // => 272
+//    IPAddress.to_string_vec(range.networks())
+//      // => ["10.0.0.5/32", "10.0.0.6/31", "10.0.0.8/29", "10.0.0.16/28",
+//      //     "10.0.0.32/27", "10.0.0.64/26", "10.0.0.128/25", "10.0.1.0/28",
+//      //     "10.0.1.16/30", "10.0.1.20/32"]
+//
+//  first and last are host addresses (/32 or /128) without zone.
+//  All the operations are computed on the bounds, so they work
+//  on the whole IPv6 space without enumerating it.
+//
+public struct IPRange: Hashable, CustomStringConvertible {
+  public let first: IPAddress;
+  public let last: IPAddress;
+
+  //  Returns nil if the addresses are of different families
+  //  or first is after last, their prefixes are ignored.
+  public init?(_ first: IPAddress, _ last: IPAddress) {
+    if (!first.is_same_kind(last) || first.address > last.address) {
+      return nil;
+    }
+    self.init(first.ip_bits, first.address, last.address);
+  }
+
+  //  The range of all the addresses of +network+
+  public init(_ network: IPAddress) {
+    self.init(network.ip_bits, network.network().address, network.broadcast().address);
+  }
+
+  init(_ ip_bits: IpBits, _ first: UInt128, _ last: UInt128) {
+    let host_prefix = Prefix(num: ip_bits.bits, ip_bits: ip_bits,
+                             net_mask: Prefix.new_netmask(ip_bits.bits, ip_bits.bits));
+    self.first = IPAddress(ip_bits: ip_bits, address: first, prefix: host_prefix);
+    self.last = IPAddress(ip_bits: ip_bits, address: last, prefix: host_prefix);
+  }
+
+  var ip_bits: IpBits {
+    return self.first.ip_bits;
+  }
+
+  //  Parses "first-last", the spaces around the dash are
+  //  optional. A string without a dash is parsed as network,
+  //  "10.0.0.0/24" is the range 10.0.0.0-10.0.0.255.
+  public static func parse(_ str: String) throws -> IPRange {
+    let dash = str.components(separatedBy: "-");
+    if (dash.count == 1) {
+      return IPRange(try IPAddress.parse(str));
+    }
+    if (dash.count > 2) {
+      let ofs = dash[0].count + dash[1].count + 1;
+      throw IPAddressParseError.invalid_range(String(str.dropFirst(ofs)), ofs);
+    }
+    let last_ofs = dash[0].count + 1;
+    let first = try IPRange.parse_bound(dash[0], 0);
+    let last = try IPRange.parse_bound(dash[1], last_ofs);
+    if (!first.is_same_kind(last)) {
+      throw IPAddressParseError.invalid_range(dash[1], last_ofs);
+    }
+    if (first.address > last.address) {
+      throw IPAddressParseError.reversed_range(str, 0);
+    }
+    return IPRange(first, last)!;
+  }
+
+  static func parse_bound(_ str: String, _ ofs: Int) throws -> IPAddress {
+    var ip: IPAddress;
+    do {
+      ip = try IPAddress.parse(str);
+    } catch let err as IPAddressParseError {
+      throw err.moved(ofs);
+    }
+    if (str.index(of: "/") != nil || ip.zone != nil) {
+      throw IPAddressParseError.invalid_range(str, ofs);
+    }
+    return ip;
+  }
+
+  public var description: String {
+    return "\(self.first.to_s())-\(self.last.to_s())";
+  }
+
+  //  The number of addresses, 2**128 for the whole IPv6 space
+  public func size() -> BigUInt {
+    return (self.last.address - self.first.address).big + BigUInt(1);
+  }
+
+  //  True if +ip+ is of the same family and all of its
+  //  network is in the range.
+  public func includes(_ ip: IPAddress) -> Bool {
+    return self.first.is_same_kind(ip) &&
+      self.first.address <= ip.network().address &&
+      ip.broadcast().address <= self.last.address;
+  }
+
+  public func includes(_ range: IPRange) -> Bool {
+    return self.first.is_same_kind(range.first) &&
+      self.first.address <= range.first.address &&
+      range.last.address <= self.last.address;
+  }
+
+  //  True if the ranges have at least one address in common
+  public func overlaps(_ range: IPRange) -> Bool {
+    return self.first.is_same_kind(range.first) &&
+      self.first.address <= range.last.address &&
+      range.first.address <= self.last.address;
+  }
+
+  //  The least number of networks which cover exactly the
+  //  range, in ascending order. Every network is the largest
+  //  one which starts at the first address not yet covered.
+  public func networks() -> [IPAddress] {
+    let bits = Int(self.ip_bits.bits);
+    var ret = [IPAddress]();
+    var cur = self.first.address;
+    while (true) {
+      let rest = self.last.address - cur;
+      // the largest block which fits before last
+      var host_bits = bits;
+      if (rest != self.ip_bits.all_ones) {
+        host_bits = 127 - (rest + UInt128.one).leadingZeroBitCount;
+      }
+      // and on which cur is aligned
+      host_bits = min(host_bits, cur.trailingZeroBitCount);
+      let prefix = self.first.prefix.from(UInt8(bits - host_bits))!;
+      ret.append(IPAddress(ip_bits: self.ip_bits, address: cur, prefix: prefix));
+      let block_last = cur | UInt128.mask(host_bits);
+      if (block_last == self.last.address) {
+        break;
+      }
+      cur = block_last + UInt128.one;
+    }
+    return ret;
+  }
+
+  //  Merges +networks+ into the least number of ranges,
+  //  adjacent and overlapping networks become one range.
+  //  IPv4 ranges come first, each family in ascending order.
+  //
+  //    IPRange.from_networks(IPAddress.to_ipaddress_vec(
+  //      ["10.0.0.0/24", "10.0.1.0/25", "10.0.3.0/24"])!)
+  //      // => [10.0.0.0-10.0.1.127, 10.0.3.0-10.0.3.255]
+  //
+  public static func from_networks(_ networks: [IPAddress]) -> [IPRange] {
+    let ranges = IPAddress.aggregate(networks.map({ $0.with_zone(nil) }))
+      .map({ IPRange($0) })
+      .sorted(by: { $0.first.lt($1.first) });
+    var ret = [IPRange]();
+    for range in ranges {
+      if (ret.isEmpty || !ret[ret.count - 1].first.is_same_kind(range.first)) {
+        ret.append(range);
+        continue;
+      }
+      let prev = ret[ret.count - 1];
+      let (after_prev, overflow) = prev.last.address.addingReportingOverflow(UInt128.one);
+      if (overflow || range.first.address > after_prev) {
+        ret.append(range);
+      } else if (range.last.address > prev.last.address) {
+        ret[ret.count - 1] = IPRange(prev.ip_bits, prev.first.address, range.last.address);
+      }
+    }
+    return ret;
+  }
+}
@@ -2,10 +2,6 @@ import Foundation
 
 import BigInt
 
-typealias Is = (_ source: IPAddress) -> Bool;
-
-typealias ToIpv4 = (_ source: IPAddress) -> IPAddress;
-
 public typealias EachFn = (_ source: IPAddress) -> Void;
 
 extension String {
@@ -29,57 +25,97 @@ extension Array {
 }
 
 
-public class ResultBigUIntParts {
-  var crunchy: BigUInt;
+public class ResultUInt128Parts {
+  var crunchy: UInt128;
   var parts: Int;
   
-  init(_ crunchy: BigUInt, _ parts: Int) {
+  init(_ crunchy: UInt128, _ parts: Int) {
     self.crunchy = crunchy;
     self.parts = parts;
-    // console.log("ResultBigUIntParts:", this);
+    // console.log("ResultUInt128Parts:", this);
   }
 }
 
-public class IPAddress : Equatable, CustomStringConvertible {
-  var ip_bits: IpBits;
-  public var host_address: BigUInt;
-  public var prefix: Prefix;
-  public var mapped: IPAddress?;
-  let vt_is_private: Is;
-  let vt_is_loopback: Is;
-  let vt_to_ipv6: ToIpv4;
-  
-  init(ip_bits: IpBits, host_address: BigUInt, prefix: Prefix,
-       mapped: IPAddress?,
-       vt_is_private: @escaping Is,
-       vt_is_loopback: @escaping Is,
-       vt_to_ipv6: @escaping ToIpv4) {
+//  IPAddress is an immutable value: every operation returns a
+//  new IPAddress and never changes the one it was called on, so
+//  an address handed to another component can't change under it.
+//
+//  An address can carry a zone (RFC 4007), the interface name or
+//  index of a scoped address like "fe80::1%eth0". The zone is part
+//  of the identity of the address: it is compared by eq and kept
+//  by every operation which derives an address of the same link
+//  from it, like network, broadcast, first, last, subnet or
+//  change_prefix. A zoned network only includes addresses of the
+//  same zone, a network without zone includes the addresses of any
+//  zone, so fe80::/10 includes "fe80::1%eth0". netmask, mapped and
+//  the conversions between IPv4 and IPv6 drop the zone.
+//
+//  The text of an IPAddress is to_string, and parses back to an
+//  equal address. Addresses sort IPv4 before IPv6, then by
+//  address, prefix and zone, see cmp:
+//
+//    IPAddress("10.0.0.1/24")!.description
+//      // => "10.0.0.1/24"
+//    [IPAddress("::1")!, IPAddress("10.0.0.2")!, IPAddress("10.0.0.1/8")!].sorted()
+//      // => [10.0.0.1/8, 10.0.0.2/32, ::1/128]
+//
+public struct IPAddress : Hashable, Comparable, LosslessStringConvertible, CustomDebugStringConvertible {
+  let ip_bits: IpBits;
+  public let address: UInt128;
+  public let prefix: Prefix;
+  public let zone: String?;
+  
+  init(ip_bits: IpBits, address: UInt128, prefix: Prefix, zone: String? = nil) {
     self.ip_bits = ip_bits;
-    self.host_address = host_address;
+    self.address = address;
     self.prefix = prefix;
-    self.mapped = mapped;
-    self.vt_is_private = vt_is_private;
-    self.vt_is_loopback = vt_is_loopback;
-    self.vt_to_ipv6 = vt_to_ipv6;
+    self.zone = zone;
+  }
+  //  Parses like IPAddress.parse, returns nil where parse
+  //  throws.
+  public init?(_ description: String) {
+    let ip = try? IPAddress.parse(description);
+    if (ip == nil) {
+      return nil;
+    }
+    self = ip!;
   }
   public var description: String {
+    return self.to_string();
+  }
+  public var debugDescription: String {
     return "<IPAddress:\(self.to_string())>";
   }
   
+  public func hash(into hasher: inout Hasher) {
+    hasher.combine(self.ip_bits.version);
+    hasher.combine(self.address);
+    hasher.combine(self.prefix.num);
+    hasher.combine(self.zone);
+  }
+  
+  //  The address as BigUInt, the storage itself is a
+  //  fixed width UInt128.
+  public var host_address: BigUInt {
+    return self.address.big;
+  }
+  
+  //  The embedded IPv4 address of an IPv4 mapped IPv6
+  //  address, see IPAddress.IPv6.Mapped
+  //
+  //    ip6 = IPAddress "::ffff:ac10:a01/120"
+  //
+  //    ip6.mapped.to_string
+  //      // => "172.16.10.1/24"
+  //
+  public var mapped: IPAddress? {
+    return Ipv6.mapped_ipv4(self);
+  }
+  
+  //  IPAddress is a value type, a copy is the same as
+  //  the original.
   public func clone()-> IPAddress {
-    var mapped: IPAddress? = nil;
-    if (self.mapped != nil) {
-      mapped = self.mapped!.clone();
-    }
-    return IPAddress(
-      ip_bits: self.ip_bits.clone(),
-      host_address: self.host_address,
-      prefix: self.prefix.clone(),
-      mapped: mapped,
-      vt_is_private: self.vt_is_private,
-      vt_is_loopback: self.vt_is_loopback,
-      vt_to_ipv6: self.vt_to_ipv6
-    );
+    return self;
   }
   
   public func lt(_ oth: IPAddress)-> Bool {
@@ -106,18 +142,37 @@ public class IPAddress : Equatable, CustomStringConvertible {
       return -1;
     }
     //let adr_diff = self.host_address - oth.host_address;
-    if (self.host_address > oth.host_address) {
+    if (self.address > oth.address) {
       return 1;
-    } else if (self.host_address < oth.host_address) {
+    } else if (self.address < oth.address) {
       return -1;
     }
-    return self.prefix.cmp(oth.prefix);
+    let prefix_cmp = self.prefix.cmp(oth.prefix);
+    if (prefix_cmp != 0) {
+      return prefix_cmp;
+    }
+    return IPAddress.cmp_zone(self.zone, oth.zone);
   }
   
-  public final class func ==(lhs: IPAddress, rhs: IPAddress) -> Bool {
+  //  Addresses without zone sort before the zoned ones
+  static func cmp_zone(_ a: String?, _ b: String?) -> Int {
+    if (a == b) {
+      return 0;
+    }
+    if (a == nil || (b != nil && a! < b!)) {
+      return -1;
+    }
+    return 1;
+  }
+  
+  public static func ==(lhs: IPAddress, rhs: IPAddress) -> Bool {
     return lhs.eq(rhs)
   }
   
+  public static func <(lhs: IPAddress, rhs: IPAddress) -> Bool {
+    return lhs.lt(rhs);
+  }
+  
   public func eq(_ other: IPAddress)-> Bool {
     // if (!!self.mapped != !!self.mapped) {
     //     return false;
@@ -130,7 +185,8 @@ public class IPAddress : Equatable, CustomStringConvertible {
     // console.log("************", this);
     return self.ip_bits.version == other.ip_bits.version &&
       self.prefix.eq(other.prefix) &&
-      self.host_address == other.host_address;
+      self.address == other.address &&
+      self.zone == other.zone;
   }
   public func ne(_ other: IPAddress) -> Bool {
     return !self.eq(other);
@@ -152,24 +208,48 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //  ip_mapped.class
   //    //=> IPAddress.IPv6.Mapped
   //
-  public class func parse(_ str: String) -> IPAddress? {
-    let colon = str.index(of: ":")
-    let dot = str.index(of: ".")
+  // Throws an IPAddressParseError which tells why and
+  // where the string is not a valid address.
+  //
+  // With ParseOptions.strict the IPv4 parts must be four
+  // decimal octets without leading zeros:
+  //
+  //  IPAddress.parse("127.1", ParseOptions.strict)
+  //    //=> throws too_few_octets
+  //
+  // A zone follows the address after a %:
+  //
+  //  ip6 = IPAddress("fe80::1%eth0/64")
+  //
+  //  ip6.zone
+  //    //=> "eth0"
+  //
+  public static func parse(_ str: String, _ options: ParseOptions = ParseOptions.lenient) throws -> IPAddress {
+    // a zone like eth0.100 may contain dots
+    let addr = str.components(separatedBy: "%")[0];
+    let colon = addr.index(of: ":")
+    let dot = addr.index(of: ".")
     if (colon != nil && dot != nil && colon! < dot!) {
-      return Ipv6Mapped.create(str);
+      do {
+        return try Ipv6Mapped.create(str, options);
+      } catch IPAddressParseError.invalid_mapped_prefix {
+        // not ::ffff:a.b.c.d, an IPv6 address with the last
+        // 32 bits in dotted form and an IPv6 prefix
+        return try Ipv6.create(str, options);
+      }
     } else {
       if (dot != nil && colon == nil) {
         // console.log("ipv4:", str);
-        return Ipv4.create(str);
+        return try Ipv4.create(str, options);
       } else if (dot == nil && colon != nil) {
         // console.log("ipv6:", str);
-        return Ipv6.create(str);
+        return try Ipv6.create(str, options);
       }
     }
-    return nil;
+    throw IPAddressParseError.unknown_format(str, 0);
   }
   
-  public class func split_at_slash(_ str: String)-> (String, String?) {
+  public static func split_at_slash(_ str: String)-> (String, String?) {
     let slash: [String] = str.trimmingCharacters(in: .whitespacesAndNewlines).components(separatedBy: "/")
     var addr = "";
     if (slash.count >= 1) {
@@ -181,19 +261,85 @@ public class IPAddress : Equatable, CustomStringConvertible {
       return (addr, nil)
     }
   }
-  public func from(_ addr: BigUInt, _ prefix: Prefix) -> IPAddress {
-    var mapped: IPAddress? = nil;
-    if (self.mapped != nil) {
-      mapped = self.mapped!.clone();
+  
+  static func leading_whitespaces(_ str: String) -> Int {
+    return str.prefix(while: { $0.isWhitespace }).count;
+  }
+  
+  // Like split_at_slash but also returns the character
+  // offsets of the address and the netmask in +str+.
+  static func split_at_slash_ofs(_ str: String) throws -> (String, Int, String?, Int) {
+    let slash: [String] = str.components(separatedBy: "/");
+    let addr = slash[0].trimmingCharacters(in: .whitespacesAndNewlines);
+    let addr_ofs = IPAddress.leading_whitespaces(slash[0]);
+    if (slash.count == 1) {
+      return (addr, addr_ofs, nil, 0);
+    }
+    let netmask = slash[1].trimmingCharacters(in: .whitespacesAndNewlines);
+    let netmask_ofs = slash[0].count + 1 + IPAddress.leading_whitespaces(slash[1]);
+    if (slash.count > 2) {
+      let rest = slash.dropFirst(2).joined(separator: "/");
+      throw IPAddressParseError.invalid_prefix("/\(rest)", slash[0].count + slash[1].count + 1);
     }
+    return (addr, addr_ofs, netmask, netmask_ofs);
+  }
+  
+  // Splits "fe80::1%eth0" at the % into the address, the
+  // zone and the offset of the zone, +ofs+ is the offset of
+  // +ip+ in the parsed string.
+  static func split_zone(_ ip: String, _ ofs: Int) throws -> (String, String?, Int) {
+    let pct: [String] = ip.components(separatedBy: "%");
+    if (pct.count == 1) {
+      return (ip, nil, 0);
+    }
+    let zone = pct.dropFirst().joined(separator: "%");
+    let zone_ofs = ofs + pct[0].count + 1;
+    if (zone.isEmpty || pct.count > 2 || zone.contains(where: { $0.isWhitespace })) {
+      throw IPAddressParseError.invalid_zone(zone, zone_ofs);
+    }
+    return (pct[0], zone, zone_ofs);
+  }
+  
+  // Throws zone_not_allowed if +ip+ carries a zone but
+  // +options+ only allow zones on link local addresses.
+  static func check_zone(_ ip: IPAddress, _ zone_ofs: Int, _ options: ParseOptions) throws -> IPAddress {
+    if (ip.zone != nil && !options.zone_on_any_address && !ip.is_zone_scope()) {
+      throw IPAddressParseError.zone_not_allowed(ip.zone!, zone_ofs);
+    }
+    return ip;
+  }
+  
+  // True for the addresses which are only unique within a
+  // link: fe80::/10 and the link local multicast ff02::/16
+  // with any flags.
+  func is_zone_scope() -> Bool {
+    if (!self.is_ipv6()) {
+      return false;
+    }
+    return (self.address >> 118) == UInt128(0x3fa) ||
+      ((self.address >> 112) & UInt128(0xff0f)) == UInt128(0xff02);
+  }
+  
+  //  Returns a copy of the address with the given zone,
+  //  nil removes the zone.
+  //
+  //    ip6 = IPAddress("fe80::1/64")
+  //
+  //    ip6.with_zone("eth0").to_string
+  //      // => "fe80::1%eth0/64"
+  //
+  public func with_zone(_ zone: String?) -> IPAddress {
+    return IPAddress(ip_bits: self.ip_bits, address: self.address, prefix: self.prefix, zone: zone);
+  }
+  public func from(_ addr: BigUInt, _ prefix: Prefix) -> IPAddress {
+    return self.from(UInt128(addr)!, prefix);
+  }
+  public func from(_ addr: UInt128, _ prefix: Prefix) -> IPAddress {
     return IPAddress(
       ip_bits: self.ip_bits,
-      host_address: addr,
-      prefix: prefix.clone(),
-      mapped: mapped,
-      vt_is_private: self.vt_is_private,
-      vt_is_loopback: self.vt_is_loopback,
-      vt_to_ipv6: self.vt_to_ipv6
+      address: addr,
+      prefix: prefix,
+      zone: self.zone
     );
   }
   
@@ -230,11 +376,11 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //   IPAddress.valid? "10.0.0.256"
   //     //=> false
   //
-  public class func is_valid(_ addr: String) -> Bool {
-    return IPAddress.is_valid_ipv4(addr) || IPAddress.is_valid_ipv6(addr);
+  public static func is_valid(_ addr: String, _ options: ParseOptions = ParseOptions.lenient) -> Bool {
+    return IPAddress.is_valid_ipv4(addr, options) || IPAddress.is_valid_ipv6(addr);
   }
   
-  class func parse_dec_str(_ str: String) -> UInt? {
+  static func parse_dec_str(_ str: String) -> UInt? {
     let part = UInt(str);
     if (part == nil) {
       // console.log("parse_dec_str:-2:", str, part);
@@ -244,7 +390,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return part;
   }
   
-  class func parse_hex_str(_ str: String)-> Int? {
+  static func parse_hex_str(_ str: String)-> Int? {
     return Int(str, radix: 16);
   }
   
@@ -259,46 +405,68 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //   IPAddress.valid_ipv4? "172.16.10.1"
   //     //=> true
   //
-  class func parse_ipv4_part(_ i: String) -> UInt8? {
+  static func parse_ipv4_part(_ i: String, _ ofs: Int, _ options: ParseOptions) throws -> UInt8 {
+    if (i.isEmpty) {
+      throw IPAddressParseError.empty_octet(i, ofs);
+    }
+    if (!options.ipv4_leading_zeros) {
+      // UInt() would accept "+010" and hide the leading zero
+      if (i.count > 3 || !i.allSatisfy({ $0.isASCII && $0.isNumber })) {
+        throw IPAddressParseError.octet_not_decimal(i, ofs);
+      }
+      if (i.count > 1 && i.hasPrefix("0")) {
+        throw IPAddressParseError.octet_leading_zero(i, ofs);
+      }
+    }
     let part = IPAddress.parse_dec_str(i);
     //console.log("i=", i, part);
-    if (part == nil || part! >= 256) {
-      return nil;
+    if (part == nil) {
+      throw IPAddressParseError.octet_not_decimal(i, ofs);
+    }
+    if (part! >= 256) {
+      throw IPAddressParseError.octet_out_of_range(i, ofs);
     }
     return UInt8(part!);
   }
   
-  class func split_to_u32(_ addr: String) -> BigUInt? {
-    var ip = BigUInt(0);
+  // +ofs+ is the character offset of +addr+ in the
+  // parsed string, it is only used for the errors.
+  static func split_to_u32(_ addr: String, _ ofs: Int = 0,
+                          _ options: ParseOptions = ParseOptions.lenient) throws -> UInt32 {
+    var ip = UInt32(0);
     var shift = 24;
     var split_addr = addr.components(separatedBy: ".");
+    var offsets = [Int]();
+    var part_ofs = ofs;
+    for i in split_addr {
+      offsets.append(part_ofs);
+      part_ofs += i.count + 1;
+    }
     if (split_addr.count > 4) {
-      return nil;
+      throw IPAddressParseError.too_many_octets(split_addr[4], offsets[4]);
     }
     let split_addr_len = split_addr.count;
+    if (split_addr_len < 4 && !options.ipv4_shorthand) {
+      throw IPAddressParseError.too_few_octets(addr, ofs);
+    }
     if (1 <= split_addr_len && split_addr_len < 4) {
-      let part = IPAddress.parse_ipv4_part(split_addr[split_addr_len - 1]);
-      if (part == nil) {
-        return nil;
-      }
-      ip = BigUInt(part!);
+      let part = try IPAddress.parse_ipv4_part(split_addr[split_addr_len - 1],
+                                               offsets[split_addr_len - 1], options);
+      ip = UInt32(part);
       split_addr = Array(split_addr.dropLast(1))
     }
-    for i in split_addr {
-      let part = IPAddress.parse_ipv4_part(i);
+    for (idx, i) in split_addr.enumerated() {
+      let part = try IPAddress.parse_ipv4_part(i, offsets[idx], options);
       // console.log("u32-", addr, i, part);
-      if (part == nil) {
-        return nil;
-      }
       //println!("{}-{}", part_num, shift);
-      ip = ip + (BigUInt(part!) << shift);
+      ip = ip | (UInt32(part) << shift);
       shift -= 8;
     }
     return ip;
   }
   
-  public class func is_valid_ipv4(_ addr: String) -> Bool {
-    return IPAddress.split_to_u32(addr) != nil
+  public static func is_valid_ipv4(_ addr: String, _ options: ParseOptions = ParseOptions.lenient) -> Bool {
+    return (try? IPAddress.split_to_u32(addr, 0, options)) != nil
   }
   
   
@@ -312,56 +480,71 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //   IPAddress.valid_ipv6? "2002.DEAD.BEEF"
   //     //=> false
   //
-  class func split_on_colon(_ addr: String) -> ResultBigUIntParts? {
-    let parts = addr.trimmingCharacters(in: .whitespacesAndNewlines).components(separatedBy: ":");
-    var ip = BigUInt(0);
+  static func split_on_colon(_ addr: String, _ ofs: Int) throws -> ResultUInt128Parts {
+    let parts = addr.components(separatedBy: ":");
+    var ip = UInt128.zero;
     if (parts.count == 1 && parts[0].isEmpty) {
-      return ResultBigUIntParts(ip, 0);
+      return ResultUInt128Parts(ip, 0);
     }
     let parts_len = parts.count;
     var shift = ((parts_len - 1) * 16);
-    for i in parts {
+    var part_ofs = ofs;
+    for (idx, i) in parts.enumerated() {
       //println!("{}={}", addr, i);
-      let part = IPAddress.parse_hex_str(i);
-      if (part == nil || part! >= 65536) {
-        return nil;
+      if (idx >= 128 / 16) {
+        throw IPAddressParseError.too_many_groups(i, part_ofs);
+      }
+      if (i.isEmpty) {
+        throw IPAddressParseError.empty_group(i, part_ofs);
+      }
+      // Int(_:radix:) would accept "+db8" and "-0"
+      if (!i.allSatisfy({ $0.isASCII && $0.isHexDigit })) {
+        throw IPAddressParseError.group_not_hex(i, part_ofs);
       }
-      ip = ip + (BigUInt(part!) << shift);
+      if (i.count > 4) {
+        throw IPAddressParseError.group_out_of_range(i, part_ofs);
+      }
+      let part = IPAddress.parse_hex_str(i)!;
+      ip = ip | (UInt128(UInt64(part)) << shift);
       shift -= 16;
+      part_ofs += i.count + 1;
     }
-    return ResultBigUIntParts(ip, parts_len);
+    return ResultUInt128Parts(ip, parts_len);
   }
   
-  class func split_to_num(_ addr: String) -> ResultBigUIntParts? {
+  // +ofs+ is the character offset of +addr+ in the
+  // parsed string, it is only used for the errors.
+  static func split_to_num(_ addr: String, _ ofs: Int = 0) throws -> ResultUInt128Parts {
     //let ip = 0;
-    let pre_post = addr.trimmingCharacters(in: .whitespacesAndNewlines).components(separatedBy: "::");
+    let trimmed = addr.trimmingCharacters(in: .whitespacesAndNewlines);
+    let base = ofs + IPAddress.leading_whitespaces(addr);
+    let pre_post = trimmed.components(separatedBy: "::");
     if (pre_post.count > 2) {
-      return nil;
+      throw IPAddressParseError.multiple_double_colons("::",
+        base + pre_post[0].count + 2 + pre_post[1].count);
     }
     if (pre_post.count == 2) {
       //println!("{}=.={}", pre_post[0], pre_post[1]);
-      let pre = IPAddress.split_on_colon(pre_post[0]);
-      if (pre == nil) {
-        return pre;
-      }
-      let post = IPAddress.split_on_colon(pre_post[1]);
-      if (post == nil) {
-        return post;
+      let pre = try IPAddress.split_on_colon(pre_post[0], base);
+      let post = try IPAddress.split_on_colon(pre_post[1], base + pre_post[0].count + 2);
+      // the :: stands for at least one group
+      if (pre.parts + post.parts >= 128 / 16) {
+        throw IPAddressParseError.too_many_groups(trimmed, base);
       }
       // println!("pre:{} post:{}", pre_parts, post_parts);
-      return ResultBigUIntParts(
-        (pre!.crunchy << (128 - (pre!.parts * 16))) + post!.crunchy, 128 / 16);
+      return ResultUInt128Parts(
+        (pre.crunchy << (128 - (pre.parts * 16))) | post.crunchy, 128 / 16);
     }
     //println!("split_to_num:no double:{}", addr);
-    let ret = IPAddress.split_on_colon(addr);
-    if (ret == nil || ret!.parts != 128 / 16) {
-      return nil;
+    let ret = try IPAddress.split_on_colon(trimmed, base);
+    if (ret.parts != 128 / 16) {
+      throw IPAddressParseError.wrong_group_count(trimmed, base);
     }
     return ret;
   }
   
-  public class func is_valid_ipv6(_ addr: String) -> Bool {
-    return IPAddress.split_to_num(addr) != nil;
+  public static func is_valid_ipv6(_ addr: String) -> Bool {
+    return (try? IPAddress.split_to_num(addr)) != nil;
   }
   
   
@@ -370,7 +553,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   // means it should be sorted lowers first and uniq
   //
   
-  class func pos_to_idx(_ pos: Int, _ len: Int) -> Int {
+  static func pos_to_idx(_ pos: Int, _ len: Int) -> Int {
     let ilen = len;
     // let ret = pos % ilen;
     let rem = ((pos % ilen) + ilen) % ilen;
@@ -378,7 +561,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return rem;
   }
   
-  public class func aggregate(_ networks: [IPAddress]) -> [IPAddress] {
+  public static func aggregate(_ networks: [IPAddress]) -> [IPAddress] {
     if (networks.count == 0) {
       return [];
     }
@@ -386,7 +569,12 @@ public class IPAddress : Equatable, CustomStringConvertible {
       // console.log("aggregate:", networks[0], networks[0].network());
       return [networks[0].network()];
     }
-    var stack = networks.map({ $0.network() }).sorted(by: { $0.lt($1) });
+    // networks of the same zone next to each other, only
+    // they are merged
+    var stack = networks.map({ $0.network() }).sorted(by: {
+      let zone_cmp = IPAddress.cmp_zone($0.zone, $1.zone);
+      return zone_cmp < 0 || (zone_cmp == 0 && $0.lt($1));
+    });
     // console.log(IPAddress.to_string_vec(stack));
     //     for i in stack {
     //         print("\(i)");
@@ -415,27 +603,26 @@ public class IPAddress : Equatable, CustomStringConvertible {
       let second = IPAddress.pos_to_idx(pos, stack_len);
       pos = pos + 1;
       //let firstUnwrap = first;
-      if (stack[first].includes(stack[second])) {
+      if (stack[first].zone == stack[second].zone && stack[first].includes(stack[second])) {
         pos = pos - 2;
         // println!("remove:1:{}:{}:{}=>{}", first, second, stack_len, pos + 1);
         let pidx = IPAddress.pos_to_idx(pos + 1, stack_len);
         stack = Array(stack[0...pidx-1] + stack.dropFirst(pidx + 1));
       } else {
-        stack[first].prefix = stack[first].prefix.sub(1)!;
+        let sup = stack[first].from(stack[first].address, stack[first].prefix.sub(1)!);
         // println!("complex:{}:{}:{}:{}:P1:{}:P2:{}", pos, stack_len,
         // first, second,
-        // stack[first].to_string(), stack[second].to_string());
-        if ((stack[first].prefix.num + 1) == stack[second].prefix.num &&
-          stack[first].includes(stack[second])) {
+        // sup.to_string(), stack[second].to_string());
+        if ((sup.prefix.num + 1) == stack[second].prefix.num &&
+          sup.zone == stack[second].zone && sup.includes(stack[second])) {
           pos = pos - 2;
           let idx = IPAddress.pos_to_idx(pos, stack_len);
-          stack[idx] = stack[first].clone(); // kaputt
+          stack[idx] = sup;
           let pidx = IPAddress.pos_to_idx(pos + 1, stack_len);
           stack = Array(stack[0...pidx-1] + stack.dropFirst(pidx + 1));
           // println!("remove-2:{}:{}", pos + 1, stack_len);
           pos = pos - 1; // backtrack
         } else {
-          stack[first].prefix = stack[first].prefix.add(1)!; //reset prefix
           // println!("easy:{}:{}=>{}", pos, stack_len, stack[first].to_string());
           pos = pos - 1; // do it with second as first
         }
@@ -446,7 +633,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   }
   
   public func parts() -> [UInt] {
-    return self.ip_bits.parts(self.host_address);
+    return self.ip_bits.parts(self.address);
   }
   
   public func parts_hex_str() -> [String] {
@@ -493,12 +680,146 @@ public class IPAddress : Equatable, CustomStringConvertible {
   }
   
   
+  //  The network named by a reverse DNS name, the opposite
+  //  of dns_reverse. A partial name covers the prefix of its
+  //  labels, 8 bits per octet in in-addr.arpa and 4 bits per
+  //  nibble in ip6.arpa. The domain is case insensitive and
+  //  may end in a dot:
+  //
+  //    IPAddress.from_dns_reverse("4.3.2.1.in-addr.arpa").to_string()
+  //      // => "1.2.3.4/32"
+  //    IPAddress.from_dns_reverse("2.1.in-addr.arpa.").to_string()
+  //      // => "1.2.0.0/16"
+  //    IPAddress.from_dns_reverse("8.b.d.0.1.0.0.2.ip6.arpa").to_string()
+  //      // => "2001:db8::/32"
+  //
+  //  The RFC 2317 child zones of dns_classless_reverse give
+  //  their network, a name inside of them the host:
+  //
+  //    IPAddress.from_dns_reverse("65.64/26.2.0.192.in-addr.arpa").to_string()
+  //      // => "192.0.2.65/32"
+  //
+  //  Throws unknown_reverse_domain for other names and
+  //  invalid_reverse_label for a label which is not a decimal
+  //  octet without leading zeros, resp. a single hex digit,
+  //  or which is one too many.
+  public static func from_dns_reverse(_ name: String) throws -> IPAddress {
+    var labels = name.components(separatedBy: ".");
+    if (labels.count > 1 && labels.last!.isEmpty) {
+      labels.removeLast();
+    }
+    var found: IpBits? = nil;
+    for bits in [IpBits.v4(), IpBits.v6()] {
+      let domain = bits.rev_domain.components(separatedBy: ".");
+      if (labels.count >= domain.count &&
+        labels.suffix(domain.count).map({ $0.lowercased() }) == domain) {
+        found = bits;
+      }
+    }
+    if (found == nil) {
+      throw IPAddressParseError.unknown_reverse_domain(name, 0);
+    }
+    let ip_bits = found!;
+    let parts = Array(labels.dropLast(ip_bits.rev_domain.components(separatedBy: ".").count));
+    let classless = parts.firstIndex(where: { $0.contains("/") || $0.contains("-") });
+    if (ip_bits.version == IpVersion.V4 && classless != nil) {
+      return try IPAddress.from_dns_classless(parts, classless!);
+    }
+    let max_parts = Int(ip_bits.bits / ip_bits.dns_bits);
+    if (parts.count > max_parts) {
+      throw IPAddressParseError.invalid_reverse_label(parts[0], 0);
+    }
+    var address = UInt128.zero;
+    var ofs = 0;
+    // the name starts with the least significant part
+    for (idx, label) in parts.enumerated() {
+      let value = IPAddress.parse_dns_label(ip_bits, label);
+      if (value == nil) {
+        throw IPAddressParseError.invalid_reverse_label(label, ofs);
+      }
+      let shift = Int(ip_bits.bits) - Int(ip_bits.dns_bits) * (parts.count - idx);
+      address = address | UInt128(UInt64(value!)) << shift;
+      ofs += label.count + 1;
+    }
+    let prefix_num = UInt8(parts.count) * ip_bits.dns_bits;
+    switch (ip_bits.version) {
+    case IpVersion.V4: return Ipv4.from_u32(address.u32, prefix_num)!;
+    case IpVersion.V6: return Ipv6.from_u128(address, prefix_num)!;
+    }
+  }
+
+  //  The network of an RFC 2317 child zone name
+  //  "net/len.c.b.a" (or "net-len") with +idx+ the index of
+  //  the "net/len" label in +parts+, or the host address of
+  //  "host.net/len.c.b.a" which must be in the network.
+  static func from_dns_classless(_ parts: [String], _ idx: Int) throws -> IPAddress {
+    var offsets = [Int]();
+    var ofs = 0;
+    for label in parts {
+      offsets.append(ofs);
+      ofs += label.count + 1;
+    }
+    let label = parts[idx];
+    if (idx > 1 || parts.count - idx != 4) {
+      throw IPAddressParseError.invalid_reverse_label(label, offsets[idx]);
+    }
+    let sep = label.firstIndex(where: { $0 == "/" || $0 == "-" })!;
+    let net = IPAddress.parse_dns_label(IpBits.v4(), String(label[..<sep]));
+    let len = IPAddress.parse_dns_label(IpBits.v4(), String(label[label.index(after: sep)...]));
+    if (net == nil || len == nil || len! <= 24 || len! >= 32) {
+      throw IPAddressParseError.invalid_reverse_label(label, offsets[idx]);
+    }
+    var address = UInt32(net!);
+    for i in 1...3 {
+      let octet = IPAddress.parse_dns_label(IpBits.v4(), parts[idx + i]);
+      if (octet == nil) {
+        throw IPAddressParseError.invalid_reverse_label(parts[idx + i], offsets[idx + i]);
+      }
+      address = address | UInt32(octet!) << (8 * i);
+    }
+    let network = Ipv4.from_u32(address, len!)!;
+    if (network.network() != network) {
+      // host bits set in front of the prefix length
+      throw IPAddressParseError.invalid_reverse_label(label, offsets[idx]);
+    }
+    if (idx == 0) {
+      return network;
+    }
+    let host = IPAddress.parse_dns_label(IpBits.v4(), parts[0]);
+    if (host == nil) {
+      throw IPAddressParseError.invalid_reverse_label(parts[0], 0);
+    }
+    let ip = Ipv4.from_u32(address & 0xffffff00 | UInt32(host!), 32)!;
+    if (!network.includes(ip)) {
+      throw IPAddressParseError.invalid_reverse_label(parts[0], 0);
+    }
+    return ip;
+  }
+
+  //  The value of one label of a reverse DNS name, a decimal
+  //  octet in in-addr.arpa or a hex nibble in ip6.arpa
+  static func parse_dns_label(_ ip_bits: IpBits, _ label: String) -> UInt8? {
+    switch (ip_bits.version) {
+    case IpVersion.V4:
+      if (label.isEmpty || label.count > 3 || (label.count > 1 && label.hasPrefix("0")) ||
+        !label.allSatisfy({ $0.isASCII && $0.isNumber })) {
+        return nil;
+      }
+      return UInt8(label);
+    case IpVersion.V6:
+      if (label.count != 1 || !label.allSatisfy({ $0.isHexDigit })) {
+        return nil;
+      }
+      return UInt8(label, radix: 16);
+    }
+  }
+  
   public func dns_parts() -> [UInt] {
     var ret: [UInt] = [UInt]();
-    var num = self.host_address;
-    let mask = BigUInt(1) << Int(self.ip_bits.dns_bits);
+    var num = self.address;
+    let mask = UInt128.mask(Int(self.ip_bits.dns_bits));
     for _ in 1...(self.ip_bits.bits / self.ip_bits.dns_bits) {
-      let part = UInt(String(num % mask))!
+      let part = UInt((num & mask).lo);
       num = num >> Int(self.ip_bits.dns_bits);
       ret.append(part);
     }
@@ -516,18 +837,20 @@ public class IPAddress : Equatable, CustomStringConvertible {
     }
     //  println!("dns_networks:{}:{}", self.to_string(), next_bit_mask);
     // dns_bits
-    let step_bit_net = BigUInt(1) << Int(self.ip_bits.bits - next_bit_mask);
-    if (step_bit_net == BigUInt(0)) {
+    let step_bit_net = UInt128.one << Int(self.ip_bits.bits - next_bit_mask);
+    if (step_bit_net.is_zero) {
       // console.log("dns_networks-2", self.to_string());
       return [self.network()];
     }
     var ret: [IPAddress] = [IPAddress]();
-    var step = self.network().host_address;
+    var step = self.network().address;
     let prefix = self.prefix.from(next_bit_mask)!;
-    while (step <= self.broadcast().host_address) {
+    // at most 2**(dns_bits-1) networks, counting avoids the
+    // overflow after the last network of the address space
+    for _ in 1...(1 << Int(next_bit_mask - self.prefix.num)) {
       // console.log("dns_networks-3", self.to_string(), step.toString(), next_bit_mask, step_bit_net.toString());
       ret.append(self.from(step, prefix));
-      step = step + step_bit_net;
+      step = step &+ step_bit_net;
     }
     return ret;
   }
@@ -656,11 +979,11 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //    IPAddress.IPv4.summarize(ip1,ip2,ip3,ip4).map{|i| i.to_string}
   //      // => ["2000:1./32","2000:2./31","2000:4./32"]
   //
-  public class func summarize(_ networks: [IPAddress]) -> [IPAddress]? {
+  public static func summarize(_ networks: [IPAddress]) -> [IPAddress]? {
     return IPAddress.aggregate(networks);
   }
   
-  public class func summarize_str(_ netstr: [String]) -> [IPAddress]? {
+  public static func summarize_str(_ netstr: [String]) -> [IPAddress]? {
     let vec = IPAddress.to_ipaddress_vec(netstr);
     // console.log(netstr, vec);
     if (vec == nil) {
@@ -678,7 +1001,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //  See IPAddress.IPv6.Unspecified for more information
   //
   public func is_unspecified() -> Bool {
-    return self.host_address == BigUInt(0);
+    return self.address.is_zero;
   }
   
   //  Returns true if the address is a loopback address
@@ -686,7 +1009,10 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //  See IPAddress.IPv6.Loopback for more information
   //
   public func is_loopback() -> Bool {
-    return (self.vt_is_loopback)(self);
+    switch (self.ip_bits.version) {
+    case IpVersion.V4: return Ipv4.ipv4_is_loopback(self);
+    case IpVersion.V6: return Ipv6.ipv6_is_loopback(self);
+    }
   }
   
   
@@ -696,7 +1022,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //
   public  func is_mapped() -> Bool {
     let ret = self.mapped != nil &&
-      (self.host_address >> 32) == ((BigUInt(1) << 16) - BigUInt(1));
+      (self.address >> 32) == UInt128(0xffff);
     // console.log("+++++++++++", self.mapped, ret);
     return ret;
   }
@@ -724,17 +1050,16 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //   IPAddress.valid_ipv4_netmask? "255.255.0.0"
   //     //=> true
   //
-  public class func is_valid_netmask(_ addr: String) -> Bool {
-    return IPAddress.parse_netmask_to_prefix(addr) != nil;
+  public static func is_valid_netmask(_ addr: String) -> Bool {
+    return (try? IPAddress.parse_netmask_to_prefix(addr)) != nil;
   }
   
-  class func netmask_to_prefix(_ nm: BigUInt, _ bits: UInt8) -> UInt8? {
+  static func netmask_to_prefix(_ nm: UInt128, _ bits: UInt8) -> UInt8? {
     var prefix : UInt8 = 0;
     var addr = nm;
     var in_host_part = true;
-    // let two = BigUInt.two();
     for _ in 1...bits {
-      let bit = addr % 2;
+      let bit = addr.lo & 1;
       // console.log(">>>", bits, bit, addr, nm);
       if (in_host_part && bit == 0) {
         prefix = prefix + 1;
@@ -749,21 +1074,31 @@ public class IPAddress : Equatable, CustomStringConvertible {
   }
   
   
-  public class func parse_netmask_to_prefix(_ netmask: String) -> UInt8? {
+  // +ofs+ is the character offset of +netmask+ in the
+  // parsed string, it is only used for the errors.
+  public static func parse_netmask_to_prefix(_ netmask: String, _ ofs: Int = 0,
+                                            _ options: ParseOptions = ParseOptions.lenient) throws -> UInt8 {
     // console.log("--1", netmask);
     let is_number = IPAddress.parse_dec_str(netmask);
     if (is_number != nil) {
       // console.log("--2", netmask, is_number);
+      if (is_number! > UInt(IpBits.v6().bits)) {
+        throw IPAddressParseError.prefix_out_of_range(netmask, ofs);
+      }
       return UInt8(is_number!);
     }
-    let my = IPAddress.parse(netmask);
+    let my = try? IPAddress.parse(netmask, options);
     // console.log("--3", netmask, my);
     if (my == nil) {
       // console.log("--4", netmask, my);
-      return nil;
+      throw IPAddressParseError.invalid_prefix(netmask, ofs);
     }
     // console.log("--5", netmask, my);
-    return IPAddress.netmask_to_prefix(my!.host_address, my!.ip_bits.bits);
+    let prefix = IPAddress.netmask_to_prefix(my!.address, my!.ip_bits.bits);
+    if (prefix == nil) {
+      throw IPAddressParseError.non_contiguous_netmask(netmask, ofs);
+    }
+    return prefix!;
   }
   
   
@@ -789,11 +1124,11 @@ public class IPAddress : Equatable, CustomStringConvertible {
     if (prefix == nil) {
       return nil;
     }
-    return self.from(self.host_address, prefix!);
+    return self.from(self.address, prefix!);
   }
   
   public func change_netmask(_ str: String) -> IPAddress? {
-    let nm = IPAddress.parse_netmask_to_prefix(str);
+    let nm = try? IPAddress.parse_netmask_to_prefix(str);
     if (nm == nil) {
       return nil;
     }
@@ -817,7 +1152,14 @@ public class IPAddress : Equatable, CustomStringConvertible {
   }
   
   public func to_s() -> String {
-    return self.ip_bits.as_compressed_string(self.host_address);
+    return self.ip_bits.as_compressed_string(self.address) + self.zone_suffix();
+  }
+  
+  func zone_suffix() -> String {
+    if (self.zone == nil) {
+      return "";
+    }
+    return "%\(self.zone!)";
   }
   
   public func to_string_uncompressed() -> String {
@@ -828,19 +1170,94 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return ret;
   }
   public func to_s_uncompressed() -> String {
-    return self.ip_bits.as_uncompressed_string(self.host_address);
+    return self.ip_bits.as_uncompressed_string(self.address) + self.zone_suffix();
+  }
+  
+  //  Returns the text of the address in the given format,
+  //  the zone is kept in every format:
+  //
+  //    ip = IPAddress("172.16.10.1/24")
+  //
+  //    ip.format(FormatOptions(prefix: PrefixFormat.netmask))
+  //      // => "172.16.10.1/255.255.255.0"
+  //
+  //    ip6 = IPAddress("2001:db8::8:800:200c:417a/64")
+  //
+  //    ip6.format(FormatOptions(groups: GroupFormat.expanded, prefix: PrefixFormat.none))
+  //      // => "2001:0db8:0000:0000:0008:0800:200c:417a"
+  //
+  //  See FormatOptions for all the options.
+  //
+  public func format(_ options: FormatOptions = FormatOptions.standard) -> String {
+    var ret = self.ip_bits.as_formatted_string(self.address, options.groups,
+                                               options.uppercase, options.embedded_ipv4);
+    ret += self.zone_suffix();
+    switch (options.prefix) {
+    case PrefixFormat.none:
+      break;
+    case PrefixFormat.length:
+      ret += "/\(self.prefix.num)";
+    case PrefixFormat.netmask:
+      ret += "/" + self.ip_bits.as_formatted_string(self.prefix.net_mask, options.groups,
+                                                    options.uppercase, false);
+    }
+    return ret;
+  }
+  
+  //  Returns the RFC 5952 text of the address, a single
+  //  zero group is not compressed:
+  //
+  //    ip6 = IPAddress("2001:db8:0:1:1:1:1:1/64")
+  //
+  //    ip6.to_string
+  //      // => "2001:db8::1:1:1:1:1/64"
+  //    ip6.to_string_canonical
+  //      // => "2001:db8:0:1:1:1:1:1/64"
+  //
+  public func to_s_canonical() -> String {
+    return self.ip_bits.as_canonical_string(self.address) + self.zone_suffix();
+  }
+  
+  public func to_string_canonical() -> String {
+    return "\(self.to_s_canonical())/\(self.prefix.num)";
+  }
+  
+  //  Checks if +text+ is already in the canonical form of
+  //  the address it denotes: RFC 5952 for IPv6, four decimal
+  //  octets without leading zeros for IPv4. A prefix is
+  //  optional but must be a decimal length.
+  //
+  //    IPAddress.is_canonical("2001:db8::1")
+  //      // => true
+  //    IPAddress.is_canonical("2001:DB8::1")
+  //      // => false
+  //    IPAddress.is_canonical("2001:db8:0:0:1::1")
+  //      // => false, the leftmost of two equal runs is compressed
+  //
+  //  IPv4 mapped addresses are canonical in hex form only,
+  //  "::ffff:a00:1" but not "::ffff:10.0.0.1".
+  //
+  public static func is_canonical(_ text: String) -> Bool {
+    let ip = try? IPAddress.parse(text);
+    if (ip == nil) {
+      return false;
+    }
+    if (text.index(of: "/") != nil) {
+      return text == ip!.to_string_canonical();
+    }
+    return text == ip!.to_s_canonical();
   }
   
   public func to_s_mapped() -> String {
     if (self.is_mapped()) {
-      return "::ffff:\(self.mapped!.to_s())";
+      return "::ffff:\(self.mapped!.to_s())\(self.zone_suffix())";
     }
     return self.to_s();
   }
   
   public func to_string_mapped() -> String {
     if (self.is_mapped()) {
-      let mapped = self.mapped!.clone();
+      let mapped = self.mapped!;
       return "\(self.to_s_mapped())/\(mapped.prefix.num)";
     }
     return self.to_string();
@@ -855,20 +1272,15 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "01111111000000000000000000000001"
   //
   public func bits() -> String {
-    let num = String(self.host_address, radix: 2);
-    var ret = "";
-    for _ in num.count...Int(self.ip_bits.bits-1) {
-      ret += "0";
-    }
-    ret += num;
-    return ret;
+    let num = self.address.to_string(2);
+    return String(repeating: "0", count: Int(self.ip_bits.bits) - num.count) + num;
   }
   public func to_hex() -> String {
-    return String(self.host_address, radix: 16);
+    return self.address.to_string(16);
   }
   
   public func netmask() -> IPAddress {
-    return self.from(self.prefix.netmask(), self.prefix);
+    return IPAddress(ip_bits: self.ip_bits, address: self.prefix.net_mask, prefix: self.prefix);
   }
   
   //  Returns the broadcast address for the given IP.
@@ -879,7 +1291,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "172.16.10.255"
   //
   public func broadcast() -> IPAddress {
-    return self.from(self.network().host_address + (self.size() - BigUInt(1)), self.prefix);
+    return self.from(self.network().address | self.prefix.host_bits_mask(), self.prefix);
     // IPv4.parse_u32(self.broadcast_u32, self.prefix)
   }
   
@@ -897,7 +1309,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //
   public func is_network() -> Bool {
     return self.prefix.num != self.ip_bits.bits &&
-      self.host_address == self.network().host_address;
+      self.address == self.network().address;
   }
   
   //  Returns a new IPv4 object with the network Int
@@ -909,24 +1321,24 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "172.16.10.0"
   //
   public func network() -> IPAddress {
-    return self.from(IPAddress.to_network(self.host_address, self.prefix.host_prefix()), self.prefix);
+    return self.from(IPAddress.to_network(self.address, self.prefix.host_prefix()), self.prefix);
   }
-  class func to_network(_ adr: BigUInt, _ host_prefix: UInt8) -> BigUInt {
-    return (adr >> Int(host_prefix)) << Int(host_prefix);
+  static func to_network(_ adr: UInt128, _ host_prefix: UInt8) -> UInt128 {
+    return adr & ~UInt128.mask(Int(host_prefix));
   }
   
   public func sub(_ other: IPAddress) -> BigUInt {
-    if (self.host_address > other.host_address) {
-      return self.host_address - other.host_address;
+    if (self.address > other.address) {
+      return (self.address - other.address).big;
     }
-    return other.host_address - self.host_address;
+    return (other.address - self.address).big;
   }
   
   public func add(_ other: IPAddress) -> [IPAddress] {
-    return IPAddress.aggregate([self.clone(), other.clone()]);
+    return IPAddress.aggregate([self, other]);
   }
   
-  public class func to_s_vec(_ vec: [IPAddress]) -> [String] {
+  public static func to_s_vec(_ vec: [IPAddress]) -> [String] {
     var ret: [String] = [String]();
     for i in vec {
       ret.append(i.to_s());
@@ -934,21 +1346,21 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return ret;
   }
   
-  public class func to_string_vec(_ vec: [IPAddress]) -> [String] {
+  public static func to_string_vec(_ vec: [IPAddress]) -> [String] {
     var ret: [String] = [String]();
     for i in vec {
       ret.append(i.to_string());
     }
     return ret;
   }
-  public class func to_string_vec(_ vec: [IPAddress]?) -> [String] {
+  public static func to_string_vec(_ vec: [IPAddress]?) -> [String] {
     return to_string_vec(vec!);
   }
   
-  public class func to_ipaddress_vec(_ vec: [String]) -> [IPAddress]? {
+  public static func to_ipaddress_vec(_ vec: [String]) -> [IPAddress]? {
     var ret: [IPAddress] = [IPAddress]();
     for ipstr in vec {
-      let ipa = IPAddress.parse(ipstr);
+      let ipa = try? IPAddress.parse(ipstr);
       if (ipa == nil) {
         return nil;
       }
@@ -976,8 +1388,15 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //    ip.first.to_s
   //      // => "192.168.100.1"
   //
+  //  An IPv4 /32 has no network and broadcast address, its
+  //  first and last host is the address itself.
+  //
   public func first() -> IPAddress {
-    return self.from(self.network().host_address + self.ip_bits.host_ofs, self.prefix);
+    let network = self.network().address;
+    if (self.broadcast().address - network < self.ip_bits.host_ofs) {
+      return self.network();
+    }
+    return self.from(network + self.ip_bits.host_ofs, self.prefix);
   }
   
   //  Like its sibling method IPv4// first, this method
@@ -1001,7 +1420,11 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "192.168.100.254"
   //
   public func last() -> IPAddress {
-    return self.from(self.broadcast().host_address - self.ip_bits.host_ofs, self.prefix);
+    let broadcast = self.broadcast().address;
+    if (broadcast - self.network().address < self.ip_bits.host_ofs) {
+      return self.broadcast();
+    }
+    return self.from(broadcast - self.ip_bits.host_ofs, self.prefix);
   }
   
   //  Iterates over all the hosts IP addresses for the given
@@ -1020,16 +1443,53 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "10.0.0.6"
   //
   public func each_host(_ fn: EachFn) {
-    var i = self.first().host_address;
-    while (i <= self.last().host_address) {
-      fn(self.from(i, self.prefix));
-      i = i + BigUInt(1);
+    for ip in self.hosts {
+      fn(ip);
     }
   }
   
+  //  The host addresses of the network as a lazy collection,
+  //  from first to last, see IPAddressCollection.
+  //
+  //    ip = IPAddress("10.0.0.1/29")
+  //
+  //    ip.hosts[0].to_s
+  //      // => "10.0.0.1"
+  //    ip.hosts[-1].to_s
+  //      // => "10.0.0.6"
+  //    ip.hosts.count
+  //      // => 6
+  //
+  //  An IPv4 /31 or /32 has no hosts.
+  //
+  public var hosts: IPAddressCollection {
+    let network = self.network().address;
+    let broadcast = self.broadcast().address;
+    let ofs = self.ip_bits.host_ofs;
+    if (broadcast - network < ofs + ofs) {
+      return IPAddressCollection(self, network, self.prefix, 0, UInt128.zero, true);
+    }
+    return IPAddressCollection(self, network + ofs, self.prefix, 0, broadcast - ofs - ofs - network, false);
+  }
+  
+  //  All the addresses of the network as a lazy collection,
+  //  the network and broadcast addresses included.
+  //
+  //    ip = IPAddress("10.0.0.1/29")
+  //
+  //    ip.addresses.reversed().first!.to_s
+  //      // => "10.0.0.7"
+  //
+  public var addresses: IPAddressCollection {
+    let network = self.network().address;
+    return IPAddressCollection(self, network, self.prefix, 0, self.broadcast().address - network, false);
+  }
+  
   public func inc() -> IPAddress? {
-    let ret = self.clone();
-    ret.host_address = ret.host_address + BigUInt(1);
+    if (self.address == self.ip_bits.all_ones) {
+      return nil;
+    }
+    let ret = self.from(self.address + UInt128.one, self.prefix);
     if (ret.lte(self.last())) {
       return ret;
     }
@@ -1037,8 +1497,10 @@ public class IPAddress : Equatable, CustomStringConvertible {
   }
   
   public func dec() -> IPAddress? {
-    let ret = self.clone();
-    ret.host_address = ret.host_address - BigUInt(1);
+    if (self.address.is_zero) {
+      return nil;
+    }
+    let ret = self.from(self.address - UInt128.one, self.prefix);
     if (ret.lte(self.first())) {
       return ret;
     }
@@ -1065,11 +1527,12 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => "10.0.0.6"
   //      // => "10.0.0.7"
   //
+  //  It visits every address, 2**128 of them for ::/0, the
+  //  addresses collection can be left at any time.
+  //
   public func each(_ fn: EachFn) {
-    var i = self.network().host_address;
-    while (i <= self.broadcast().host_address) {
-      fn(self.from(i, self.prefix));
-      i = i + BigUInt(1);
+    for ip in self.addresses {
+      fn(ip);
     }
   }
   
@@ -1138,8 +1601,9 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //
   public func includes(_ oth: IPAddress) -> Bool {
     let ret = self.is_same_kind(oth) &&
+      (self.zone == nil || self.zone == oth.zone) &&
       self.prefix.num <= oth.prefix.num &&
-      self.network().host_address == IPAddress.to_network(oth.host_address, self.prefix.host_prefix());
+      self.network().address == IPAddress.to_network(oth.address, self.prefix.host_prefix());
     // println!("includes:{}=={}=>{}", self.to_string(), oth.to_string(), ret);
     return ret
   }
@@ -1164,7 +1628,8 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return true;
   }
   //  Checks if an IPv4 address objects belongs
-  //  to a private network RFC1918
+  //  to a private network RFC1918, or an IPv6 one to
+  //  the unique local addresses fc00::/7
   //
   //  Example:
   //
@@ -1173,7 +1638,79 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //      // => true
   //
   public func is_private() -> Bool {
-    return self.vt_is_private(self);
+    switch (self.ip_bits.version) {
+    case IpVersion.V4: return Ipv4.ipv4_is_private(self);
+    case IpVersion.V6: return Ipv6.ipv6_is_private(self);
+    }
+  }
+  
+  //  The most specific entry of the IANA Special-Purpose
+  //  Address Registries which includes the whole network,
+  //  nil for ordinary unicast and multicast space.
+  //
+  //    ip = IPAddress("192.0.0.9")
+  //
+  //    ip.special_purpose().name
+  //      // => "Port Control Protocol Anycast"
+  //
+  public func special_purpose() -> SpecialPurpose? {
+    return SpecialPurpose.lookup(self).last;
+  }
+  
+  //  True if the registries mark the address as globally
+  //  reachable, or do not list it. Where the most specific
+  //  entry says N/A, like 2001::/32 (Teredo), the entry
+  //  around it decides.
+  //
+  //    IPAddress("8.8.8.8").global?
+  //      // => true
+  //    IPAddress("100.64.0.1").global?
+  //      // => false
+  //
+  public func is_global() -> Bool {
+    for entry in SpecialPurpose.lookup(self).reversed() {
+      if (entry.globally_reachable != nil) {
+        return entry.globally_reachable!;
+      }
+    }
+    return true;
+  }
+  
+  //  169.254.0.0/16 (RFC 3927) and fe80::/10 (RFC 4291)
+  public func is_link_local() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.link_local, self);
+  }
+  
+  //  192.0.2.0/24, 198.51.100.0/24, 203.0.113.0/24 (RFC 5737),
+  //  2001:db8::/32 (RFC 3849) and 3fff::/20 (RFC 9637)
+  public func is_documentation() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.documentation, self);
+  }
+  
+  //  The carrier grade NAT space 100.64.0.0/10 (RFC 6598)
+  public func is_shared() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.shared, self);
+  }
+  
+  //  198.18.0.0/15 (RFC 2544) and 2001:2::/48 (RFC 5180)
+  public func is_benchmarking() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.benchmarking, self);
+  }
+  
+  //  240.0.0.0/4 (RFC 1112) and the IPv6 blocks which IANA
+  //  lists as "Reserved by IETF", ::/8 among them
+  public func is_reserved() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.reserved, self);
+  }
+  
+  //  fc00::/7 (RFC 4193)
+  public func is_unique_local() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.unique_local, self);
+  }
+  
+  //  224.0.0.0/4 (RFC 5771) and ff00::/8 (RFC 4291)
+  public func is_multicast() -> Bool {
+    return SpecialPurpose.includes(SpecialPurpose.multicast, self);
   }
   
   
@@ -1226,10 +1763,11 @@ public class IPAddress : Equatable, CustomStringConvertible {
     return dup;
   }
   public func split(_ subnets: UInt) -> [IPAddress]? {
-    if (subnets == 0 || BigUInt(1 << self.prefix.host_prefix()) <= subnets) {
+    if (subnets == 0 || self.size() <= BigUInt(subnets)) {
       return nil;
     }
-    let networks = self.subnet(self.newprefix(UInt8(subnets))!.num);
+    let prefix = self.prefix.add(IPAddress.count_bits(subnets));
+    let networks = prefix.flatMap({ self.subnet($0.num) });
     if (networks == nil) {
       return networks;
     }
@@ -1272,7 +1810,7 @@ public class IPAddress : Equatable, CustomStringConvertible {
     // for _ in new_prefix..self.prefix.num {
     //     new_ip = new_ip << 1;
     // }
-    return self.from(self.host_address, self.prefix.from(new_prefix)!).network();
+    return self.from(self.address, self.prefix.from(new_prefix)!).network();
   }
   
   //  This method implements the subnetting function
@@ -1296,20 +1834,67 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //  The resulting Int of subnets will of course always be
   //  a power of two.
   //
+  //  Returns nil if there are more subnets than an array can
+  //  hold, use subnets for them.
+  //
   public func subnet(_ subprefix: UInt8) -> [IPAddress]? {
+    let subnets = self.subnets(subprefix);
+    if (subnets == nil || subnets!.size() > BigUInt(Int.max)) {
+      return nil;
+    }
+    return Array(subnets!);
+  }
+  
+  //  Like subnet but returns a lazy collection, which never
+  //  holds more than the subnet it yields, so the /64s of
+  //  a /32 can be walked or indexed:
+  //
+  //    ip = IPAddress("2001:db8::/32")
+  //
+  //    ip.subnets(64)![1].to_string
+  //      // => "2001:db8:0:1::/64"
+  //    ip.subnets(64)![-1].to_string
+  //      // => "2001:db8:ffff:ffff::/64"
+  //    ip.subnets(64)!.size()
+  //      // => 4294967296
+  //
+  public func subnets(_ subprefix: UInt8) -> IPAddressCollection? {
     if (subprefix < self.prefix.num || self.ip_bits.bits < subprefix) {
       return nil;
     }
-    var ret: [IPAddress] = [];
-    var net = self.network();
-    net.prefix = net.prefix.from(subprefix)!;
-    for _ in 1...(1 << Int(subprefix - self.prefix.num)) {
-      ret.append(net.clone());
-      net = net.from(net.host_address, net.prefix);
-      let size = net.size();
-      net.host_address = net.host_address + size;
+    let prefix = self.prefix.from(subprefix)!;
+    return IPAddressCollection(self, self.network().address, prefix, Int(prefix.host_prefix()),
+                               UInt128.mask(Int(subprefix - self.prefix.num)), false);
+  }
+  
+  //  Returns the subnet with prefix +prefix+ at position
+  //  +index+, counted from zero, without building the ones
+  //  before it. Returns nil if +prefix+ is shorter than the
+  //  prefix of the network or there are not so many subnets.
+  //
+  //    ip = IPAddress("2001:db8:1::/48")
+  //
+  //    ip.subnet(at: 4711, prefix: 64).to_string
+  //      // => "2001:db8:1:1267::/64"
+  //
+  public func subnet(at index: UInt128, prefix: UInt8) -> IPAddress? {
+    return self.subnets(prefix)?.at(index);
+  }
+  
+  //  The opposite of subnet(at:prefix:), returns the position
+  //  of +subnet+ among the subnets of its prefix, or nil if
+  //  it is not included in the network.
+  //
+  //    ip = IPAddress("2001:db8:1::/48")
+  //
+  //    ip.index(of: IPAddress("2001:db8:1:1267::/64"))
+  //      // => 4711
+  //
+  public func index(of subnet: IPAddress) -> UInt128? {
+    if (!self.includes(subnet)) {
+      return nil;
     }
-    return ret;
+    return (subnet.network().address - self.network().address) >> Int(subnet.prefix.host_prefix());
   }
   
   //  Return the ip address in a format compatible
@@ -1322,18 +1907,32 @@ public class IPAddress : Equatable, CustomStringConvertible {
   //    ip.to_ipv6
   //      // => "ac10:0a01"
   //
+  //  The 32 bits and the prefix number are taken as they are,
+  //  to_ipv6(_:) converts with a real embedding and prefix.
+  //
   public func to_ipv6() -> IPAddress {
-    return self.vt_to_ipv6(self);
+    switch (self.ip_bits.version) {
+    case IpVersion.V4: return Ipv4.to_ipv6(self);
+    case IpVersion.V6: return Ipv6.to_ipv6(self);
+    }
   }
   
+  //  The prefix of the smallest subnets of which there are
+  //  at least +num+ in the network.
   public func newprefix(_ num: UInt8) -> Prefix? {
-    for i in num...self.ip_bits.bits-1 {
-      let a = Float(Int(log2(Float(i))));
-      if (a == log2(Float(i))) {
-        return self.prefix.add(UInt8(a));
-      }
+    if (num == 0) {
+      return nil;
     }
-    return nil
+    return self.prefix.add(IPAddress.count_bits(UInt(num)));
+  }
+  
+  //  The number of bits needed to count +num+ networks,
+  //  the logarithm base 2 of +num+ rounded up.
+  static func count_bits(_ num: UInt) -> UInt8 {
+    if (num <= 1) {
+      return 0;
+    }
+    return UInt8(UInt.bitWidth - (num - 1).leadingZeroBitCount);
   }
   
   
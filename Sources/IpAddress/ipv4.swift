@@ -8,83 +8,66 @@ import BigInt
 
 public class Ipv4 {
   public class func from_int(_ addr: BigUInt, _ prefix_num: UInt8)-> IPAddress? {
+    if ((addr >> 32) != BigUInt(0)) {
+      return nil;
+    }
+    return Ipv4.from_u32(UInt32(truncatingIfNeeded: addr), prefix_num);
+  }
+  
+  public class func from_u32(_ addr: UInt32, _ prefix_num: UInt8)-> IPAddress? {
     let prefix = Prefix32.create(prefix_num);
     if (prefix == nil) {
       return nil;
     }
     return IPAddress(
       ip_bits: IpBits.v4(),
-      host_address: addr,
-      prefix: prefix!,
-      mapped: nil,
-      vt_is_private: Ipv4.ipv4_is_private,
-      vt_is_loopback: Ipv4.ipv4_is_loopback,
-      vt_to_ipv6: Ipv4.to_ipv6
+      address: UInt128(addr),
+      prefix: prefix!
     );
   }
   
-  public class func create(_ str: String)-> IPAddress? {
+  public class func create(_ str: String, _ options: ParseOptions = ParseOptions.lenient) throws -> IPAddress {
     // console.log("create:v4:", str);
     // let enable = str == "0.0.0.0/0";
-    let (ip, netmask) = IPAddress.split_at_slash(str);
-    if (!IPAddress.is_valid_ipv4(ip)) {
-      // enable && console.log("xx1");
-      return nil;
-    }
+    let (ip_zone, ip_ofs, netmask, netmask_ofs) = try IPAddress.split_at_slash_ofs(str);
+    let (ip, zone, zone_ofs) = try IPAddress.split_zone(ip_zone, ip_ofs);
+    let split_number = try IPAddress.split_to_u32(ip, ip_ofs, options);
     var ip_prefix_num = UInt8(32);
     if (netmask != nil) {
       //  netmask is defined
-      let tmp = IPAddress.parse_netmask_to_prefix(netmask!);
-      if (tmp == nil) {
-        // enable && console.log("xx2");
-        return nil;
-      }
-      ip_prefix_num = tmp!
+      ip_prefix_num = try IPAddress.parse_netmask_to_prefix(netmask!, netmask_ofs, options);
       //if ip_prefix.ip_bits.version
     }
     let ip_prefix = Prefix32.create(ip_prefix_num);
     if (ip_prefix == nil) {
       // enable && console.log("xx3");
-      return nil;
-    }
-    let split_number = IPAddress.split_to_u32(ip);
-    if (split_number == nil) {
-      // enable && console.log("xx4");
-      return nil;
+      throw IPAddressParseError.prefix_out_of_range(netmask ?? "", netmask_ofs);
     }
     // console.log(">>>>>>>", ip, ip_prefix);
-    return IPAddress(
+    return try IPAddress.check_zone(IPAddress(
       ip_bits: IpBits.v4(),
-      host_address: split_number!,
+      address: UInt128(split_number),
       prefix: ip_prefix!,
-      mapped: nil,
-      vt_is_private: Ipv4.ipv4_is_private,
-      vt_is_loopback: Ipv4.ipv4_is_loopback,
-      vt_to_ipv6: Ipv4.to_ipv6
-    );
+      zone: zone
+    ), zone_ofs, options);
   }
   
   public class func ipv4_is_private(_ my: IPAddress) -> Bool {
-    return [IPAddress.parse("10.0.0.0/8")!,
-            IPAddress.parse("169.254.0.0/16")!,
-            IPAddress.parse("172.16.0.0/12")!,
-            IPAddress.parse("192.168.0.0/16")!]
+    return [try! IPAddress.parse("10.0.0.0/8"),
+            try! IPAddress.parse("172.16.0.0/12"),
+            try! IPAddress.parse("192.168.0.0/16")]
       .firstIndex(where: { $0.includes(my) }) != nil
   }
   
   public class func ipv4_is_loopback(_ my: IPAddress) -> Bool {
-    return IPAddress.parse("127.0.0.0/8")!.includes(my);
+    return try! IPAddress.parse("127.0.0.0/8").includes(my);
   }
   
   public class func to_ipv6(_ ia: IPAddress) -> IPAddress {
     return IPAddress(
       ip_bits: IpBits.v6(),
-      host_address: ia.host_address,
-      prefix: Prefix128.create(ia.prefix.num)!,
-      mapped: nil,
-      vt_is_private: Ipv6.ipv6_is_private,
-      vt_is_loopback: Ipv6.ipv6_is_loopback,
-      vt_to_ipv6: Ipv6.to_ipv6
+      address: ia.address,
+      prefix: Prefix128.create(ia.prefix.num)!
     );
   }
   
@@ -101,7 +84,7 @@ public class Ipv4 {
   //
   public class func is_class_a(_ my: IPAddress) -> Bool {
     // console.log("is_class_a:", my.to_string(), BigUInt(0x80000000), my.is_ipv4());
-    return my.is_ipv4() && my.host_address < BigUInt(0x80000000);
+    return my.is_ipv4() && my.address.u32 < 0x80000000;
   }
   
   //  Checks whether the ip address belongs to a
@@ -117,8 +100,8 @@ public class Ipv4 {
   //
   public class func is_class_b(_ my: IPAddress) -> Bool {
     return my.is_ipv4() &&
-      BigUInt(0x80000000) <= (my.host_address) &&
-      my.host_address < BigUInt(0xc0000000);
+      0x80000000 <= my.address.u32 &&
+      my.address.u32 < 0xc0000000;
   }
   
   //  Checks whether the ip address belongs to a
@@ -134,8 +117,8 @@ public class Ipv4 {
   //
   public class func is_class_c(_ my: IPAddress) -> Bool {
     return my.is_ipv4() &&
-      BigUInt(0xc0000000) <= my.host_address &&
-      my.host_address < BigUInt(0xe0000000);
+      0xc0000000 <= my.address.u32 &&
+      my.address.u32 < 0xe0000000;
   }
   
   
@@ -161,21 +144,14 @@ public class Ipv4 {
   //  Note that classes C, D and E will all have a default
   //  prefix of /24 or 255.255.255.0
   //
-  public class func parse_classful(_ ip_si: String) -> IPAddress? {
-    if (!IPAddress.is_valid_ipv4(ip_si)) {
-      return nil;
-    }
-    let o_ip = IPAddress.parse(ip_si);
-    if (o_ip == nil) {
-      return o_ip;
-    }
-    let ip = o_ip!;
+  public class func parse_classful(_ ip_si: String) throws -> IPAddress {
+    let ip = Ipv4.from_u32(try IPAddress.split_to_u32(ip_si), 32)!;
     if (Ipv4.is_class_a(ip)) {
-      ip.prefix = Prefix32.create(8)!;
+      return ip.change_prefix(8)!;
     } else if (Ipv4.is_class_b(ip)) {
-      ip.prefix = Prefix32.create(16)!;
+      return ip.change_prefix(16)!;
     } else if (Ipv4.is_class_c(ip)) {
-      ip.prefix = Prefix32.create(24)!;
+      return ip.change_prefix(24)!;
     }
     return ip;
   }
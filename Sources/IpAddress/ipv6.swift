@@ -72,55 +72,49 @@ public class Ipv6 {
     return Ipv6.from_int(num!, prefix);
   }
   
+  //  Checks that an address in ::ffff:0:0/96 carries a valid
+  //  IPv4 address and prefix, returns nil otherwise.
   public class func enhance_if_mapped(_ ip: IPAddress) -> IPAddress? {
-    // console.log("------A");
-    // println!("real mapped {:x} {:x}", &ip.host_address, ip.host_address.clone().shr(32));
-    if (ip.is_mapped()) {
-      // console.log("------B");
-      return ip;
-    }
-    // console.log("------C", ip);
-    let ipv6_top_96bit = ip.host_address >> 32
-    // console.log("------D", ip);
-    if (ipv6_top_96bit == BigUInt(0xffff)) {
-      // console.log("------E");
-      let num = ip.host_address % (BigUInt(1) << 32);
-      // console.log("------F");
-      if (num == BigUInt(0)) {
-        return ip;
-      }
-      //println!("ip:{},{:x}", ip.to_string(), num);
-      let ipv4_bits = IpBits.v4();
-      if (ipv4_bits.bits < ip.prefix.host_prefix()) {
-        //println!("enhance_if_mapped-2:{}:{}", ip.to_string(), ip.prefix.host_prefix());
-        return nil;
-      }
-      // console.log("------G");
-      let mapped = Ipv4.from_int(num, ipv4_bits.bits - ip.prefix.host_prefix());
-      // console.log("------H");
-      if (mapped == nil) {
-        // println!("enhance_if_mapped-3");
-        return mapped;
-      }
-      // println!("real mapped!!!!!={}", mapped.clone().to_string());
-      ip.mapped = mapped;
+    if ((ip.address >> 32) == UInt128(0xffff) && ip.address.u32 != 0 &&
+      IpBits.v4().bits < ip.prefix.host_prefix()) {
+      //println!("enhance_if_mapped-2:{}:{}", ip.to_string(), ip.prefix.host_prefix());
+      return nil;
     }
     return ip;
   }
   
+  //  Returns the IPv4 address embedded in an IPv4 mapped
+  //  IPv6 address (::ffff:a.b.c.d), the IPv4 prefix is the
+  //  IPv6 prefix minus 96.
+  public class func mapped_ipv4(_ ip: IPAddress) -> IPAddress? {
+    if (!ip.is_ipv6() || (ip.address >> 32) != UInt128(0xffff)) {
+      return nil;
+    }
+    let num = ip.address.u32;
+    let ipv4_bits = IpBits.v4();
+    if (num == 0 || ipv4_bits.bits < ip.prefix.host_prefix()) {
+      return nil;
+    }
+    return Ipv4.from_u32(num, ipv4_bits.bits - ip.prefix.host_prefix());
+  }
+  
   public class func from_int(_ adr: BigUInt, _ prefix_num: UInt8) -> IPAddress? {
+    let num = UInt128(adr);
+    if (num == nil) {
+      return nil;
+    }
+    return Ipv6.from_u128(num!, prefix_num);
+  }
+  
+  public class func from_u128(_ adr: UInt128, _ prefix_num: UInt8) -> IPAddress? {
     let prefix = Prefix128.create(prefix_num);
     if (prefix == nil) {
       return nil;
     }
     let ret = Ipv6.enhance_if_mapped(IPAddress(
       ip_bits: IpBits.v6(),
-      host_address: adr,
-      prefix: prefix!,
-      mapped: nil,
-      vt_is_private: Ipv6.ipv6_is_private,
-      vt_is_loopback: Ipv6.ipv6_is_loopback,
-      vt_to_ipv6: Ipv6.to_ipv6
+      address: adr,
+      prefix: prefix!
     ));
     //console.log("from_int:", adr, prefix, ret);
     return ret;
@@ -142,60 +136,96 @@ public class Ipv6 {
   //
   //    ip6 = IPAddress "2001:db8::8:800:200c:417a/64"
   //
-  public class func create(_ str: String) -> IPAddress? {
+  //  A link local address can be followed by its zone:
+  //
+  //    ip6 = IPAddress "fe80::1%eth0/64"
+  //
+  //  The last 32 bits can be written as dotted IPv4 address,
+  //  the prefix is still the IPv6 prefix:
+  //
+  //    ip6 = IPAddress "64:ff9b::192.0.2.0/120"
+  //
+  public class func create(_ str: String, _ options: ParseOptions = ParseOptions.lenient) throws -> IPAddress {
     // console.log("1>>>>>>>>>", str);
-    let (ip, o_netmask) = IPAddress.split_at_slash(str);
+    let (ip_zone, ip_ofs, o_netmask, netmask_ofs) = try IPAddress.split_at_slash_ofs(str);
+    let (ip, zone, zone_ofs) = try IPAddress.split_zone(ip_zone, ip_ofs);
     // console.log("2>>>>>>>>>", str);
-    if (IPAddress.is_valid_ipv6(ip)) {
-      // console.log("3>>>>>>>>>", str);
-      let o_num = IPAddress.split_to_num(ip);
-      if (o_num == nil) {
-        // console.log("ipv6_create-1", str);
-        return nil;
-      }
-      // console.log("4>>>>>>>>>", str);
-      var netmask : UInt8 = 128;
-      if (o_netmask != nil) {
-        let tmp = IPAddress.parse_dec_str(o_netmask!);
-        if (tmp == nil) {
-          // console.log("ipv6_create-2", str);
-          return nil;
-        }
-        netmask = UInt8(tmp!)
+    let num = try Ipv6.split_to_num_embedded(ip, ip_ofs, options);
+    // console.log("4>>>>>>>>>", str);
+    var netmask : UInt8 = 128;
+    if (o_netmask != nil) {
+      let tmp = IPAddress.parse_dec_str(o_netmask!);
+      if (tmp == nil) {
+        // console.log("ipv6_create-2", str);
+        throw IPAddressParseError.invalid_prefix(o_netmask!, netmask_ofs);
       }
-      // console.log("5>>>>>>>>>", str);
-      let prefix = Prefix128.create(netmask);
-      if (prefix == nil) {
+      if (tmp! > UInt(IpBits.v6().bits)) {
         // console.log("ipv6_create-3", str);
-        return nil;
+        throw IPAddressParseError.prefix_out_of_range(o_netmask!, netmask_ofs);
       }
-      //console.log("6>>>>>>>>>", str, prefix.num, o_netmask, netmask);
-      return Ipv6.enhance_if_mapped(IPAddress(
-        ip_bits: IpBits.v6(),
-        host_address: o_num!.crunchy,
-        prefix: prefix!,
-        mapped: nil,
-        vt_is_private: Ipv6.ipv6_is_private,
-        vt_is_loopback: Ipv6.ipv6_is_loopback,
-        vt_to_ipv6: Ipv6.to_ipv6
-      ));
-    } else {
-      // console.log("ipv6_create-4", str);
-      return nil;
+      netmask = UInt8(tmp!)
     }
+    //console.log("6>>>>>>>>>", str, prefix.num, o_netmask, netmask);
+    let ret = Ipv6.enhance_if_mapped(IPAddress(
+      ip_bits: IpBits.v6(),
+      address: num,
+      prefix: Prefix128.create(netmask)!,
+      zone: zone
+    ));
+    if (ret == nil) {
+      // mapped addresses need a prefix of at least 96
+      throw IPAddressParseError.prefix_out_of_range(o_netmask ?? "", netmask_ofs);
+    }
+    return try IPAddress.check_zone(ret!, zone_ofs, options);
   } //  pub fn initialize
   
+  //  Like IPAddress.split_to_num, but the last group may be
+  //  a dotted IPv4 address standing for the last 32 bits,
+  //  "64:ff9b::192.0.2.1" or "1:2:3:4:5:6:10.0.0.1".
+  static func split_to_num_embedded(_ ip: String, _ ofs: Int, _ options: ParseOptions) throws -> UInt128 {
+    let last_colon = ip.lastIndex(of: ":");
+    if (ip.index(of: ".") == nil || last_colon == nil) {
+      return try IPAddress.split_to_num(ip, ofs).crunchy;
+    }
+    let ipv4_str = String(ip[ip.index(after: last_colon!)...]);
+    let ipv4_num = try IPAddress.split_to_u32(ipv4_str, ofs + ip.count - ipv4_str.count, options);
+    // the IPv4 part replaced by two zero groups
+    let ipv6_str = String(ip.dropLast(ipv4_str.count));
+    let upper = try IPAddress.split_to_num("\(ipv6_str)0:0", ofs);
+    return upper.crunchy | UInt128(ipv4_num);
+  }
+  
+  //  The address of +interface_id+ in the network +prefix+,
+  //  which must be IPv6 and at most a /64. The address keeps
+  //  the prefix and the zone of the network.
+  //
+  //    Ipv6.from_prefix(IPAddress("2001:db8::/64")!, interface_id: 0x021a2bfffe3c4d5e)!.to_string()
+  //      // => "2001:db8::21a:2bff:fe3c:4d5e/64"
+  //
+  public class func from_prefix(_ prefix: IPAddress, interface_id: UInt64) -> IPAddress? {
+    if (!prefix.is_ipv6() || prefix.prefix.num > 64) {
+      return nil;
+    }
+    return prefix.from(UInt128(hi: prefix.network().address.hi, lo: interface_id), prefix.prefix);
+  }
+  
+  //  The SLAAC address of +mac+ in the network +prefix+, with
+  //  the modified EUI-64 interface identifier of RFC 4291
+  public class func from_prefix(_ prefix: IPAddress, mac: Eui48) -> IPAddress? {
+    return Ipv6.from_prefix(prefix, interface_id: mac.interface_id());
+  }
+  
   public class func to_ipv6(_ ia: IPAddress) -> IPAddress {
-    return ia.clone();
+    return ia;
   }
   
   public class func ipv6_is_loopback(_ my: IPAddress) -> Bool {
     // console.log("*************", my.host_address, BigUInt.one());
-    return my.host_address == BigUInt(1);
+    return my.address == UInt128.one;
   }
   
   public class func ipv6_is_private(_ my: IPAddress) -> Bool {
-    return IPAddress.parse("fd00::/8")!.includes(my);
+    return try! IPAddress.parse("fc00::/7").includes(my);
   }
   
 }
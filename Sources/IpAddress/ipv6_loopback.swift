@@ -49,6 +49,6 @@ public class Ipv6Loopback {
   //      // => "::1/128"
   //
   public class func create() -> IPAddress {
-    return Ipv6.from_int(BigUInt(1), 128)!;
+    return Ipv6.from_u128(UInt128.one, 128)!;
   }
 }
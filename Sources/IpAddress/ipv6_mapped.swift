@@ -80,79 +80,41 @@ public class Ipv6Mapped {
   //    ip6.to_string
   //      // => "::ffff:13.1.68.3"
   //
-  public class func create(_ str: String) -> IPAddress? {
+  public class func create(_ str: String, _ options: ParseOptions = ParseOptions.lenient) throws -> IPAddress {
     // console.log("mapped-1");
-    let (ip, o_netmask) = IPAddress.split_at_slash(str);
+    let (ip_zone, ip_ofs, o_netmask, netmask_ofs) = try IPAddress.split_at_slash_ofs(str);
+    let (ip, zone, zone_ofs) = try IPAddress.split_zone(ip_zone, ip_ofs);
     let split_colon = ip.components(separatedBy: ":");
     if (split_colon.count <= 1) {
       // console.log("mapped-2");
-      return nil;
-    }
-    // if split_colon.get(0).unwrap().len() > 0 {
-    //     // println!("---1a");
-    //     return Err(format!("not mapped format-2: {}", &str));
-    // }
-    // let mapped: Option<IPAddress> = None;
-    var netmask = "";
-    if (o_netmask != nil) {
-      netmask = "/\(o_netmask!)";
+      throw IPAddressParseError.unknown_format(ip, ip_ofs);
     }
     let ipv4_str = split_colon[split_colon.count - 1];
-    if (IPAddress.is_valid_ipv4(ipv4_str)) {
-      let ipv4 = IPAddress.parse("\(ipv4_str)\(netmask)");
-      if (ipv4 == nil) {
-        // console.log("mapped-3");
-        return ipv4;
-      }
-      //mapped = Some(ipv4.unwrap());
-      let addr = ipv4!;
-      let ipv6_bits = IpBits.v6();
-      let part_mod = BigUInt(ipv6_bits.part_mod);
-      let up_addr = addr.host_address;
-      let down_addr = addr.host_address;
-      
-      var rebuild_ipv6 = "";
-      var colon = "";
-      for i in stride(from: 0, to: split_colon.count-1, by: 1) {
-        rebuild_ipv6 += colon;
-        rebuild_ipv6 += split_colon[i];
-        colon = ":";
-      }
-      rebuild_ipv6 += colon;
-      let high_part = String((up_addr >> Int(IpBits.v6().part_bits)) % part_mod, radix: 16);
-      let low_part = String(down_addr % part_mod, radix: 16);
-      let bits = ipv6_bits.bits - addr.prefix.host_prefix();
-      let rebuild_ipv4 = "\(high_part):\(low_part)/\(bits)";
-      rebuild_ipv6 += rebuild_ipv4;
-      
-      // console.log("-----A", rebuild_ipv6, part_mod);
-      let r_ipv6 = IPAddress.parse(rebuild_ipv6);
-      if (r_ipv6 == nil) {
-        // println!("---3|{}", &rebuild_ipv6);
-        // console.log("mapped-4");
-        return r_ipv6;
-      }
-      if (r_ipv6!.is_mapped()) {
-        // console.log("mapped-5");
-        return r_ipv6;
-      }
-      let ipv6 = r_ipv6!;
-      let p96bit = ipv6.host_address >> 32;
-      if (p96bit != BigUInt(0)) {
-        // println!("---4|{}", &rebuild_ipv6);
-        //console.log("mapped-6",ipv6.host_address, p96bit, BigUInt(0));
-        return nil;
-      }
-      let rr_ipv6 = IPAddress.parse("::ffff:\(rebuild_ipv4)");
-      if (rr_ipv6 == nil) {
-        // println!("---3|{}", &rebuild_ipv6);
-        //console.log("mapped-7");
-        return nil;
+    let ipv4_ofs = ip_ofs + ip.count - ipv4_str.count;
+    let ipv4_num = try IPAddress.split_to_u32(ipv4_str, ipv4_ofs, options);
+    // the groups in front of the IPv4 part, with the
+    // IPv4 part replaced by two zero groups
+    let ipv6_str = String(ip.dropLast(ipv4_str.count));
+    let upper = try IPAddress.split_to_num("\(ipv6_str)0:0", ip_ofs);
+    let p96bit = upper.crunchy >> 32;
+    if (!p96bit.is_zero && p96bit != UInt128(0xffff)) {
+      // console.log("mapped-6",ipv6.host_address, p96bit, BigUInt(0));
+      throw IPAddressParseError.invalid_mapped_prefix(ipv6_str, ip_ofs);
+    }
+    var ipv4_prefix = IpBits.v4().bits;
+    if (o_netmask != nil) {
+      ipv4_prefix = try IPAddress.parse_netmask_to_prefix(o_netmask!, netmask_ofs, options);
+      if (ipv4_prefix > IpBits.v4().bits) {
+        throw IPAddressParseError.prefix_out_of_range(o_netmask!, netmask_ofs);
       }
-      // console.log("mapped-8");
-      return rr_ipv6;
     }
-    // console.log("mapped-9");
-    return nil;
+    // console.log("mapped-8");
+    let ipv6_bits = IpBits.v6();
+    return try IPAddress.check_zone(IPAddress(
+      ip_bits: ipv6_bits,
+      address: (UInt128(0xffff) << 32) | UInt128(ipv4_num),
+      prefix: Prefix128.create(ipv6_bits.bits - (IpBits.v4().bits - ipv4_prefix))!,
+      zone: zone
+    ), zone_ofs, options);
   }
 }
@@ -52,7 +52,7 @@ public class Ipv6Unspec {
   //       // => => "::/128"
   //
   public class func create() -> IPAddress {
-    return IPAddress.parse("::")!;
+    return try! IPAddress.parse("::");
   }
   //  class IPv6::Unspecified
 }
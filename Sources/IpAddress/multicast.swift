@@ -0,0 +1,208 @@
+//  The scope of a multicast address, the scope field of IPv6
+//  addresses (RFC 7346). IPv4 groups get the scope of their
+//  block: 224.0.0.0/24 is link local, 239.255.0.0/16 site
+//  local, 239.192.0.0/14 organization local, the rest of
+//  239.0.0.0/8 admin local (RFC 2365) and everything else
+//  global.
+public enum MulticastScope: UInt8 {
+  case interface_local = 1
+  case link_local = 2
+  case realm_local = 3
+  case admin_local = 4
+  case site_local = 5
+  case organization_local = 8
+  case global = 14
+}
+
+//  The flags of an IPv6 multicast address, ff<flags><scope>::
+public struct MulticastFlags: Equatable {
+  //  R, the address embeds a rendezvous point (RFC 3956)
+  public let rendezvous: Bool;
+  //  P, the address is based on a unicast prefix (RFC 3306)
+  public let prefix: Bool;
+  //  T, the address is not permanently assigned by IANA
+  public let transient: Bool;
+}
+
+//  Multicast groups and their mapping to Ethernet:
+//
+//    let group = IPAddress.multicast_from_prefix(IPAddress("2001:db8:beef::/48")!,
+//                                                0x1234, MulticastScope.global)!
+//
+//    group.to_s()
+//      // => "ff3e:30:2001:db8:beef::1234"
+//    group.unicast_prefix()!.to_string()
+//      // => "2001:db8:beef::/48"
+//    IPAddress("2001:db8::1:2:3")!.solicited_node()!.to_s()
+//      // => "ff02::1:ff02:3"
+//    IPAddress("239.1.2.3")!.multicast_mac()
+//      // => "01:00:5e:01:02:03"
+//
+extension IPAddress {
+  static let ipv4_scopes: [(IPAddress, MulticastScope)] = [
+    (try! IPAddress.parse("224.0.0.0/24"), MulticastScope.link_local),
+    (try! IPAddress.parse("239.255.0.0/16"), MulticastScope.site_local),
+    (try! IPAddress.parse("239.192.0.0/14"), MulticastScope.organization_local),
+    (try! IPAddress.parse("239.0.0.0/8"), MulticastScope.admin_local),
+  ];
+
+  static let solicited_node_prefix = try! IPAddress.parse("ff02::1:ff00:0/104");
+
+  //  The 4 bit field at +ofs+ bits from the top of an
+  //  IPv6 address
+  func nibble(_ ofs: Int) -> UInt8 {
+    return UInt8(((self.address >> (124 - ofs)) & UInt128(UInt64(0xf))).lo);
+  }
+
+  //  The scope of a multicast address, nil if the address is
+  //  not multicast or its scope value is not assigned.
+  public func multicast_scope() -> MulticastScope? {
+    if (!self.is_multicast()) {
+      return nil;
+    }
+    if (self.is_ipv6()) {
+      return MulticastScope(rawValue: self.nibble(12));
+    }
+    for (net, scope) in IPAddress.ipv4_scopes {
+      if (net.includes(self)) {
+        return scope;
+      }
+    }
+    return MulticastScope.global;
+  }
+
+  //  The R, P and T flags of an IPv6 multicast address
+  public func multicast_flags() -> MulticastFlags? {
+    if (!self.is_ipv6() || !self.is_multicast()) {
+      return nil;
+    }
+    let flags = self.nibble(8);
+    return MulticastFlags(rendezvous: flags & 4 != 0, prefix: flags & 2 != 0, transient: flags & 1 != 0);
+  }
+
+  //  Builds the RFC 3306 group ff3<scope>:0:<prefix>:<group_id>
+  //  of the unicast network +prefix+, which can be at most a
+  //  /64. Returns nil for IPv4 or longer prefixes.
+  public static func multicast_from_prefix(_ prefix: IPAddress, _ group_id: UInt32,
+                                           _ scope: MulticastScope) -> IPAddress? {
+    if (!prefix.is_ipv6() || prefix.prefix.num == 0 || prefix.prefix.num > 64) {
+      return nil;
+    }
+    return IPAddress.multicast_with_prefix(0x3, 0, prefix.prefix.num, prefix.network().address, group_id, scope);
+  }
+
+  //  Builds the RFC 3956 group ff7<scope>:<riid><plen>:<prefix>:<group_id>
+  //  which embeds the rendezvous point +rp+. The prefix of +rp+
+  //  is the length of the embedded prefix, at most 64, and only
+  //  the last 4 bits of the address after it may be set (RIID):
+  //
+  //    IPAddress.multicast_embedded_rp(IPAddress("2001:db8:beef:feed::1/64")!,
+  //                                    0x1234, MulticastScope.global)!.to_s()
+  //      // => "ff7e:140:2001:db8:beef:feed::1234"
+  //
+  public static func multicast_embedded_rp(_ rp: IPAddress, _ group_id: UInt32,
+                                           _ scope: MulticastScope) -> IPAddress? {
+    if (!rp.is_ipv6() || rp.prefix.num == 0 || rp.prefix.num > 64) {
+      return nil;
+    }
+    let riid = rp.address & UInt128(UInt64(0xf));
+    if (rp.network().address | riid != rp.address) {
+      return nil;
+    }
+    return IPAddress.multicast_with_prefix(0x7, UInt8(riid.lo), rp.prefix.num, rp.network().address,
+                                           group_id, scope);
+  }
+
+  static func multicast_with_prefix(_ flags: UInt8, _ riid: UInt8, _ plen: UInt8, _ network: UInt128,
+                                    _ group_id: UInt32, _ scope: MulticastScope) -> IPAddress {
+    let address = UInt128(UInt64(0xff)) << 120 |
+      UInt128(UInt64(flags)) << 116 |
+      UInt128(UInt64(scope.rawValue)) << 112 |
+      UInt128(UInt64(riid)) << 104 |
+      UInt128(UInt64(plen)) << 96 |
+      (network >> 64) << 32 |
+      UInt128(group_id);
+    return IPAddress(ip_bits: IpBits.v6(), address: address, prefix: Prefix128.create(128)!);
+  }
+
+  //  The plen field of a multicast address with the P flag,
+  //  nil without the flag or for the SSM range (plen 0).
+  func multicast_plen() -> UInt8? {
+    let flags = self.multicast_flags();
+    if (flags == nil || !flags!.prefix) {
+      return nil;
+    }
+    let plen = UInt8(((self.address >> 96) & UInt128(UInt64(0xff))).lo);
+    if (plen == 0 || plen > 64) {
+      return nil;
+    }
+    return plen;
+  }
+
+  //  The unicast network embedded in an RFC 3306 or RFC 3956
+  //  group, nil for other addresses.
+  public func unicast_prefix() -> IPAddress? {
+    let plen = self.multicast_plen();
+    if (plen == nil) {
+      return nil;
+    }
+    let network = (self.address >> 32) << 64;
+    return IPAddress(ip_bits: IpBits.v6(), address: network, prefix: Prefix128.create(plen!)!).network();
+  }
+
+  //  The group id, the last 32 bits of an RFC 3306 or
+  //  RFC 3956 group, nil for other addresses.
+  public func multicast_group_id() -> UInt32? {
+    if (self.multicast_plen() == nil) {
+      return nil;
+    }
+    return self.address.u32;
+  }
+
+  //  The rendezvous point embedded in an RFC 3956 group, with
+  //  the embedded prefix length as prefix.
+  //
+  //    IPAddress("ff7e:140:2001:db8:beef:feed::1234")!.rendezvous_point()!.to_string()
+  //      // => "2001:db8:beef:feed::1/64"
+  //
+  public func rendezvous_point() -> IPAddress? {
+    let prefix = self.unicast_prefix();
+    if (prefix == nil || !self.multicast_flags()!.rendezvous) {
+      return nil;
+    }
+    let riid = UInt128(UInt64(self.nibble(20)));
+    return prefix!.from(prefix!.address | riid, prefix!.prefix);
+  }
+
+  //  The solicited-node multicast group of an IPv6 unicast
+  //  address (RFC 4291), ff02::1:ff followed by its last 24
+  //  bits. The zone is kept.
+  public func solicited_node() -> IPAddress? {
+    if (!self.is_ipv6() || self.is_multicast()) {
+      return nil;
+    }
+    let low = self.address & UInt128.mask(24);
+    return self.from(IPAddress.solicited_node_prefix.address | low, Prefix128.create(128)!);
+  }
+
+  //  The Ethernet group address of a multicast address,
+  //  01:00:5e and the last 23 bits for IPv4 (RFC 1112),
+  //  33:33 and the last 32 bits for IPv6 (RFC 2464).
+  public func multicast_mac() -> String? {
+    if (!self.is_multicast()) {
+      return nil;
+    }
+    var mac: UInt64;
+    if (self.is_ipv4()) {
+      mac = 0x01005e000000 | (self.address.lo & 0x7fffff);
+    } else {
+      mac = 0x333300000000 | (self.address.lo & 0xffffffff);
+    }
+    var parts = [String]();
+    for i in stride(from: 40, through: 0, by: -8) {
+      let part = String((mac >> UInt64(i)) & 0xff | 0x100, radix: 16);
+      parts.append(String(part.dropFirst(1)));
+    }
+    return parts.joined(separator: ":");
+  }
+}
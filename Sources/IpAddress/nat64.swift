@@ -0,0 +1,108 @@
+//  IPv4-embedded IPv6 addresses of RFC 6052, as used by NAT64,
+//  DNS64 and 464XLAT. The IPv4 address follows the NAT64
+//  prefix, which is a /32, /40, /48, /56, /64 or /96, and
+//  skips the u-octet (bits 64 to 71) which stays zero:
+//
+//    let ip = IPAddress("192.0.2.33")!
+//
+//    ip.to_nat64()!.format(FormatOptions(embedded_ipv4: true))
+//      // => "64:ff9b::192.0.2.33/128"
+//    ip.to_nat64(IPAddress("2001:db8:100::/40")!)!.to_s()
+//      // => "2001:db8:1c0:2:21::"
+//    IPAddress("2001:db8:1c0:2:21::")!.nat64_ipv4(IPAddress("2001:db8:100::/40")!)!.to_s()
+//      // => "192.0.2.33"
+//
+extension IPAddress {
+  //  The Well-Known Prefix 64:ff9b::/96
+  public static let nat64_well_known_prefix = try! IPAddress.parse("64:ff9b::/96");
+
+  //  The prefix lengths defined by RFC 6052
+  public static let nat64_prefix_lengths: [UInt8] = [32, 40, 48, 56, 64, 96];
+
+  //  The IPv6 prefix length covering the first +ipv4_bits+ bits
+  //  of an IPv4 address embedded behind a /+plen+ prefix
+  static func nat64_prefix_num(_ plen: UInt8, _ ipv4_bits: UInt8) -> UInt8 {
+    if (plen == 96 || plen + ipv4_bits <= 64) {
+      return plen + ipv4_bits;
+    }
+    return plen + ipv4_bits + 8;
+  }
+
+  static func is_nat64_prefix(_ prefix: IPAddress) -> Bool {
+    return prefix.is_ipv6() && IPAddress.nat64_prefix_lengths.contains(prefix.prefix.num);
+  }
+
+  //  The IPv4-embedded IPv6 address of this IPv4 address behind
+  //  the NAT64 +prefix+, nil if this is not IPv4 or the prefix
+  //  length is not one of RFC 6052. A host gives a /128, the
+  //  prefix of an IPv4 network is carried over:
+  //
+  //    IPAddress("192.0.2.0/24")!.to_nat64()!.to_string()
+  //      // => "64:ff9b::c000:200/120"
+  //
+  public func to_nat64(_ prefix: IPAddress = IPAddress.nat64_well_known_prefix) -> IPAddress? {
+    if (!self.is_ipv4() || !IPAddress.is_nat64_prefix(prefix)) {
+      return nil;
+    }
+    let plen = prefix.prefix.num;
+    let ipv4 = UInt64(self.address.u32);
+    var address = prefix.network().address;
+    if (plen == 96) {
+      address = address | UInt128(ipv4);
+    } else {
+      // the bits in front of the u-octet and the ones behind it
+      let before = UInt64(64 - plen);
+      address = address | UInt128(ipv4 >> (32 - before)) << 64 |
+        UInt128(ipv4 & ((1 << (32 - before)) - 1)) << Int(24 + before);
+    }
+    var prefix_num: UInt8 = 128;
+    if (self.prefix.num < 32) {
+      prefix_num = IPAddress.nat64_prefix_num(plen, self.prefix.num);
+    }
+    return IPAddress(ip_bits: IpBits.v6(), address: address, prefix: Prefix128.create(prefix_num)!);
+  }
+
+  //  The IPv4 address embedded in this address by the NAT64
+  //  +prefix+. nil if the address is outside of the prefix,
+  //  the u-octet is not zero or the prefix length does not
+  //  fit an IPv4 prefix. A /128 gives a /32:
+  //
+  //    IPAddress("64:ff9b::192.0.2.33")!.nat64_ipv4()!.to_string()
+  //      // => "192.0.2.33/32"
+  //
+  public func nat64_ipv4(_ prefix: IPAddress = IPAddress.nat64_well_known_prefix) -> IPAddress? {
+    if (!IPAddress.is_nat64_prefix(prefix) || !prefix.includes(self)) {
+      return nil;
+    }
+    let plen = prefix.prefix.num;
+    var ipv4: UInt64;
+    if (plen == 96) {
+      ipv4 = UInt64(self.address.u32);
+    } else {
+      if ((self.address >> 56) & UInt128(UInt64(0xff)) != UInt128.zero) {
+        return nil;
+      }
+      let before = UInt64(64 - plen);
+      let upper = self.address.hi & ((1 << before) - 1);
+      let lower = (self.address >> Int(24 + before)).lo & ((1 << (32 - before)) - 1);
+      ipv4 = upper << (32 - before) | lower;
+    }
+    var ipv4_bits: UInt8? = nil;
+    if (self.prefix.num == 128) {
+      ipv4_bits = 32;
+    }
+    for bits in UInt8(0)..<UInt8(32) where IPAddress.nat64_prefix_num(plen, bits) == self.prefix.num {
+      ipv4_bits = bits;
+    }
+    if (ipv4_bits == nil) {
+      return nil;
+    }
+    return Ipv4.from_u32(UInt32(ipv4), ipv4_bits!);
+  }
+
+  //  True if the address lies in the NAT64 +prefix+ and
+  //  embeds an IPv4 address.
+  public func is_nat64(_ prefix: IPAddress = IPAddress.nat64_well_known_prefix) -> Bool {
+    return self.nat64_ipv4(prefix) != nil;
+  }
+}
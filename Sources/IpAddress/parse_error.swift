@@ -0,0 +1,160 @@
+//  Why a string could not be parsed into an IPAddress, an
+//  IPRange, an Eui48 or an Eui64, or a reverse DNS name could
+//  not be read by IPAddress.from_dns_reverse.
+//
+//  Every case carries the offending substring and its character
+//  offset (starting at 0) within the string given to the parser:
+//
+//    do {
+//      _ = try IPAddress.parse("10.0.0.256/24")
+//    } catch let err as IPAddressParseError {
+//      err.description
+//        // => "octet out of range '256' at column 8"
+//      err.offset
+//        // => 7
+//    }
+//
+public enum IPAddressParseError: Error, Equatable, CustomStringConvertible {
+  // neither an IPv4 nor an IPv6 address
+  case unknown_format(String, Int)
+  // IPv4, split_to_u32
+  case too_many_octets(String, Int)
+  case too_few_octets(String, Int)
+  case empty_octet(String, Int)
+  case octet_not_decimal(String, Int)
+  case octet_out_of_range(String, Int)
+  case octet_leading_zero(String, Int)
+  // IPv6, split_to_num and split_on_colon
+  case multiple_double_colons(String, Int)
+  case empty_group(String, Int)
+  case group_not_hex(String, Int)
+  case group_out_of_range(String, Int)
+  case wrong_group_count(String, Int)
+  case too_many_groups(String, Int)
+  // IPv4 mapped IPv6, the groups in front of the IPv4 part
+  // are neither ::ffff nor zero
+  case invalid_mapped_prefix(String, Int)
+  // prefix and netmask, parse_netmask_to_prefix
+  case invalid_prefix(String, Int)
+  case prefix_out_of_range(String, Int)
+  case non_contiguous_netmask(String, Int)
+  // zone, the part behind the %
+  case invalid_zone(String, Int)
+  case zone_not_allowed(String, Int)
+  // IPRange, "first - last"
+  case invalid_range(String, Int)
+  case reversed_range(String, Int)
+  // Eui48 and Eui64
+  case invalid_eui_group(String, Int)
+  case wrong_eui_length(String, Int)
+  // reverse DNS names, from_dns_reverse
+  case unknown_reverse_domain(String, Int)
+  case invalid_reverse_label(String, Int)
+
+  //  The offending substring
+  public var text: String {
+    return self.parts.0;
+  }
+
+  //  Character offset of the offending substring
+  public var offset: Int {
+    return self.parts.1;
+  }
+
+  var parts: (String, Int) {
+    switch (self) {
+    case .unknown_format(let text, let ofs): return (text, ofs);
+    case .too_many_octets(let text, let ofs): return (text, ofs);
+    case .too_few_octets(let text, let ofs): return (text, ofs);
+    case .empty_octet(let text, let ofs): return (text, ofs);
+    case .octet_not_decimal(let text, let ofs): return (text, ofs);
+    case .octet_out_of_range(let text, let ofs): return (text, ofs);
+    case .octet_leading_zero(let text, let ofs): return (text, ofs);
+    case .multiple_double_colons(let text, let ofs): return (text, ofs);
+    case .empty_group(let text, let ofs): return (text, ofs);
+    case .group_not_hex(let text, let ofs): return (text, ofs);
+    case .group_out_of_range(let text, let ofs): return (text, ofs);
+    case .wrong_group_count(let text, let ofs): return (text, ofs);
+    case .too_many_groups(let text, let ofs): return (text, ofs);
+    case .invalid_mapped_prefix(let text, let ofs): return (text, ofs);
+    case .invalid_prefix(let text, let ofs): return (text, ofs);
+    case .prefix_out_of_range(let text, let ofs): return (text, ofs);
+    case .non_contiguous_netmask(let text, let ofs): return (text, ofs);
+    case .invalid_zone(let text, let ofs): return (text, ofs);
+    case .zone_not_allowed(let text, let ofs): return (text, ofs);
+    case .invalid_range(let text, let ofs): return (text, ofs);
+    case .reversed_range(let text, let ofs): return (text, ofs);
+    case .invalid_eui_group(let text, let ofs): return (text, ofs);
+    case .wrong_eui_length(let text, let ofs): return (text, ofs);
+    case .unknown_reverse_domain(let text, let ofs): return (text, ofs);
+    case .invalid_reverse_label(let text, let ofs): return (text, ofs);
+    }
+  }
+
+  //  The same error with the offset moved by +ofs+, for a
+  //  string which was parsed as part of a longer one.
+  func moved(_ ofs: Int) -> IPAddressParseError {
+    let (text, pos) = self.parts;
+    switch (self) {
+    case .unknown_format: return .unknown_format(text, pos + ofs);
+    case .too_many_octets: return .too_many_octets(text, pos + ofs);
+    case .too_few_octets: return .too_few_octets(text, pos + ofs);
+    case .empty_octet: return .empty_octet(text, pos + ofs);
+    case .octet_not_decimal: return .octet_not_decimal(text, pos + ofs);
+    case .octet_out_of_range: return .octet_out_of_range(text, pos + ofs);
+    case .octet_leading_zero: return .octet_leading_zero(text, pos + ofs);
+    case .multiple_double_colons: return .multiple_double_colons(text, pos + ofs);
+    case .empty_group: return .empty_group(text, pos + ofs);
+    case .group_not_hex: return .group_not_hex(text, pos + ofs);
+    case .group_out_of_range: return .group_out_of_range(text, pos + ofs);
+    case .wrong_group_count: return .wrong_group_count(text, pos + ofs);
+    case .too_many_groups: return .too_many_groups(text, pos + ofs);
+    case .invalid_mapped_prefix: return .invalid_mapped_prefix(text, pos + ofs);
+    case .invalid_prefix: return .invalid_prefix(text, pos + ofs);
+    case .prefix_out_of_range: return .prefix_out_of_range(text, pos + ofs);
+    case .non_contiguous_netmask: return .non_contiguous_netmask(text, pos + ofs);
+    case .invalid_zone: return .invalid_zone(text, pos + ofs);
+    case .zone_not_allowed: return .zone_not_allowed(text, pos + ofs);
+    case .invalid_range: return .invalid_range(text, pos + ofs);
+    case .reversed_range: return .reversed_range(text, pos + ofs);
+    case .invalid_eui_group: return .invalid_eui_group(text, pos + ofs);
+    case .wrong_eui_length: return .wrong_eui_length(text, pos + ofs);
+    case .unknown_reverse_domain: return .unknown_reverse_domain(text, pos + ofs);
+    case .invalid_reverse_label: return .invalid_reverse_label(text, pos + ofs);
+    }
+  }
+
+  public var reason: String {
+    switch (self) {
+    case .unknown_format: return "not an IPv4 or IPv6 address";
+    case .too_many_octets: return "more than four octets";
+    case .too_few_octets: return "less than four octets";
+    case .empty_octet: return "empty octet";
+    case .octet_not_decimal: return "octet is not a decimal number";
+    case .octet_out_of_range: return "octet out of range";
+    case .octet_leading_zero: return "octet with leading zero";
+    case .multiple_double_colons: return "more than one '::'";
+    case .empty_group: return "empty group";
+    case .group_not_hex: return "group is not a hex number";
+    case .group_out_of_range: return "group out of range";
+    case .wrong_group_count: return "not eight groups";
+    case .too_many_groups: return "too many groups";
+    case .invalid_mapped_prefix: return "IPv4 mapped address not in ::ffff:0:0/96";
+    case .invalid_prefix: return "neither a prefix length nor a netmask";
+    case .prefix_out_of_range: return "prefix out of range";
+    case .non_contiguous_netmask: return "netmask is not contiguous";
+    case .invalid_zone: return "invalid zone";
+    case .zone_not_allowed: return "zone on an address which is not link local";
+    case .invalid_range: return "not two addresses of the same family without prefix";
+    case .reversed_range: return "first address after the last";
+    case .invalid_eui_group: return "group is not two hex digits, or four in dotted notation";
+    case .wrong_eui_length: return "not 6 bytes for a MAC or 8 for an EUI-64";
+    case .unknown_reverse_domain: return "name not in in-addr.arpa or ip6.arpa";
+    case .invalid_reverse_label: return "label is not an octet in in-addr.arpa or a hex digit in ip6.arpa, or one too many";
+    }
+  }
+
+  public var description: String {
+    return "\(self.reason) '\(self.text)' at column \(self.offset + 1)";
+  }
+}
@@ -0,0 +1,40 @@
+//  Controls how forgiving the parsers are with the IPv4
+//  notation and with zones. Every parser defaults to lenient,
+//  which is the behaviour of the original ruby ipaddress gem:
+//
+//    IPAddress.is_valid_ipv4("10.1")
+//      // => true
+//    IPAddress.is_valid_ipv4("10.1", ParseOptions.strict)
+//      // => false
+//    IPAddress.is_valid_ipv4("010.0.0.1", ParseOptions.strict)
+//      // => false
+//
+//  Strict mode is meant for user supplied input like firewall
+//  rules, where "10.1" is more likely a typo than 10.0.0.1.
+//
+public struct ParseOptions: Equatable {
+  //  Accept less than four octets, "10.1" is 10.0.0.1
+  //  and "127.1" is 127.0.0.1
+  public let ipv4_shorthand: Bool;
+  //  Accept octets with leading zeros, "010" is read as
+  //  decimal 10 and not as octal 8. Otherwise an octet is
+  //  1 to 3 ASCII digits, without a sign.
+  public let ipv4_leading_zeros: Bool;
+  //  Accept a zone on any address, otherwise only the link
+  //  local IPv6 addresses fe80::/10 and ff02::/16 (any flags)
+  //  may carry one, "fe80::1%eth0" but not "2001:db8::1%eth0"
+  public let zone_on_any_address: Bool;
+
+  public init(ipv4_shorthand: Bool, ipv4_leading_zeros: Bool, zone_on_any_address: Bool = true) {
+    self.ipv4_shorthand = ipv4_shorthand;
+    self.ipv4_leading_zeros = ipv4_leading_zeros;
+    self.zone_on_any_address = zone_on_any_address;
+  }
+
+  public static let lenient = ParseOptions(ipv4_shorthand: true, ipv4_leading_zeros: true,
+                                           zone_on_any_address: true);
+  //  Exactly four decimal octets without leading zeros and
+  //  zones only on link local IPv6 addresses
+  public static let strict = ParseOptions(ipv4_shorthand: false, ipv4_leading_zeros: false,
+                                          zone_on_any_address: false);
+}
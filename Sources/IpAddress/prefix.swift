@@ -2,28 +2,68 @@
 //import IpBits from './ip_bits';
 import BigInt
 
-typealias From = (_ source: Prefix,_ num: UInt8) -> Prefix?;
-
-public class Prefix {
+//  The text of a Prefix is its netmask, which unlike the
+//  bare length also tells the family:
+//
+//    Prefix32.create(24)!.description
+//      // => "255.255.255.0"
+//    Prefix("ffff:ffff:ffff:ffff::")!.num
+//      // => 64
+//
+//  Prefixes sort IPv4 before IPv6 and then by length.
+public struct Prefix: Hashable, Comparable, LosslessStringConvertible, CustomDebugStringConvertible {
   public let num: UInt8;
   public let ip_bits: IpBits;
-  let net_mask: BigUInt;
-  let vt_from: From;
+  let net_mask: UInt128;
   
-  init(num: UInt8, ip_bits: IpBits, net_mask: BigUInt, vt_from: @escaping From) {
+  init(num: UInt8, ip_bits: IpBits, net_mask: UInt128) {
     self.num = num;
     self.ip_bits = ip_bits;
     self.net_mask = net_mask;
-    self.vt_from = vt_from;
   }
   
+  //  Parses a contiguous netmask like "255.255.255.0",
+  //  returns nil for anything else.
+  public init?(_ description: String) {
+    let ip = try? IPAddress.parse(description, ParseOptions.strict);
+    if (ip == nil || ip!.zone != nil || ip!.prefix.num != ip!.ip_bits.bits) {
+      return nil;
+    }
+    let num = IPAddress.netmask_to_prefix(ip!.address, ip!.ip_bits.bits);
+    if (num == nil) {
+      return nil;
+    }
+    self = ip!.prefix.from(num!)!;
+  }
+  
+  public var description: String {
+    return self.to_ip_str();
+  }
+  
+  public var debugDescription: String {
+    switch (self.ip_bits.version) {
+    case IpVersion.V4: return "<Prefix32:\(self.num)>";
+    case IpVersion.V6: return "<Prefix128:\(self.num)>";
+    }
+  }
+  
+  public func hash(into hasher: inout Hasher) {
+    hasher.combine(self.ip_bits.version);
+    hasher.combine(self.num);
+  }
+  
+  public static func ==(lhs: Prefix, rhs: Prefix) -> Bool {
+    return lhs.eq(rhs);
+  }
+  
+  public static func <(lhs: Prefix, rhs: Prefix) -> Bool {
+    return lhs.cmp(rhs) < 0;
+  }
+  
+  //  Prefix is a value type, a copy is the same as
+  //  the original.
   public func clone() ->  Prefix {
-    return Prefix(
-      num: self.num,
-      ip_bits: self.ip_bits,
-      net_mask: self.net_mask,
-      vt_from: self.vt_from
-    );
+    return self;
   }
   
   public func eq(_ other: Prefix)-> Bool {
@@ -52,7 +92,10 @@ public class Prefix {
   }
   
   public func from(_ num: UInt8)-> Prefix? {
-    return (self.vt_from)(self, num);
+    switch (self.ip_bits.version) {
+    case IpVersion.V4: return Prefix32.from(self, num);
+    case IpVersion.V6: return Prefix128.from(self, num);
+    }
   }
   
   public func to_ip_str() -> String {
@@ -63,18 +106,12 @@ public class Prefix {
     return BigUInt(1) << Int(self.ip_bits.bits - self.num);
   }
   
-  public class func new_netmask(_ prefix: UInt8, _ bits: UInt8) -> BigUInt {
-    var mask = BigUInt(0);
-    let host_prefix = bits - prefix;
-    for i in stride(from:UInt8(0), to: prefix, by: 1) {
-      // console.log(">>>", i, host_prefix, mask);
-      mask = mask + (BigUInt(1) << Int(host_prefix + i));
-    }
-    return mask
+  public static func new_netmask(_ prefix: UInt8, _ bits: UInt8) -> UInt128 {
+    return UInt128.mask(Int(bits)) ^ UInt128.mask(Int(bits - prefix));
   }
   
   public func netmask() -> BigUInt {
-    return self.net_mask;
+    return self.net_mask.big;
   }
   
   public func get_prefix() -> UInt8 {
@@ -91,11 +128,11 @@ public class Prefix {
   //      // => "0.0.0.255"
   //
   public func host_mask() -> BigUInt {
-    var ret = BigUInt(0);
-    for _ in 1...(self.ip_bits.bits - self.num) {
-      ret = (ret << 1) + BigUInt(1);
-    }
-    return ret;
+    return self.host_bits_mask().big;
+  }
+  
+  func host_bits_mask() -> UInt128 {
+    return UInt128.mask(Int(self.host_prefix()));
   }
   
   
@@ -123,7 +160,7 @@ public class Prefix {
   //          "0000000000000000000000000000000000000000000000000000000000000000"
   //
   public func bits() -> String {
-    return String(self.netmask(), radix: 2);
+    return self.net_mask.to_string(2);
   }
   // #[allow(dead_code)]
   // public net_mask(&self) -> BigUint {
@@ -142,10 +179,15 @@ public class Prefix {
   }
   
   public func add_prefix(_ other: Prefix) -> Prefix? {
-    return self.from(self.get_prefix() + other.get_prefix());
+    return self.add(other.get_prefix());
   }
   
+  //  Returns nil if the sum is longer than the bits of
+  //  the family.
   public func add(_ other: UInt8) -> Prefix? {
+    if (other > self.host_prefix()) {
+      return nil;
+    }
     return self.from(self.get_prefix() + other)
   }
   
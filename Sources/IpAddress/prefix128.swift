@@ -21,8 +21,7 @@ public class Prefix128 {
       return  Prefix(
         num: num,
         ip_bits: ip_bits,
-        net_mask: Prefix.new_netmask(num, bits),
-        vt_from: Prefix128.from // vt_to_ip_str: _TO_IP_STR
+        net_mask: Prefix.new_netmask(num, bits)
       );
     }
     return nil;
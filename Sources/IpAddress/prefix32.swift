@@ -14,8 +14,7 @@ public class Prefix32 {
       return Prefix(
         num: num,
         ip_bits: ip_bits,
-        net_mask: Prefix.new_netmask(num, bits),
-        vt_from: Prefix32.from
+        net_mask: Prefix.new_netmask(num, bits)
       );
     }
     return nil;
@@ -0,0 +1,97 @@
+//  Interface identifiers which do not reveal the MAC of the
+//  host: the stable privacy ones of RFC 7217, the same in a
+//  network for a given interface and secret key but unrelated
+//  across networks, and the random temporary ones of RFC 8981.
+//
+//    let net = try! Ipv6.create("2001:db8:1:2::/64")
+//
+//    Ipv6.stable_privacy(net, interface: "eth0", secret_key: Array(0..<16))!.to_string()
+//      // => "2001:db8:1:2:d4d9:22:8d78:1ba9/64"
+//    Ipv6.temporary(net)!.to_string()
+//      // => "2001:db8:1:2:<64 random bits>/64"
+//
+//  Neither ever returns one of the reserved interface
+//  identifiers of RFC 5453.
+extension Ipv6 {
+  //  True for the interface identifiers reserved by RFC 5453:
+  //  the subnet-router anycast 0, the IANA Ethernet block
+  //  0200:5eff:fe00:0 - 0200:5eff:feff:ffff (with the Proxy
+  //  Mobile IPv6 0200:5eff:fe00:5213) and the subnet anycast
+  //  fdff:ffff:ffff:ff80 - fdff:ffff:ffff:ffff of RFC 2526.
+  public class func is_reserved_interface_id(_ iid: UInt64) -> Bool {
+    return iid == 0 ||
+      iid >> 24 == 0x02005efffe ||
+      (iid >= 0xfdffffffffffff80 && iid <= 0xfdffffffffffffff);
+  }
+
+  //  The RFC 7217 RID, the last 64 bits of
+  //  SHA-256(Prefix | Net_Iface | Network_ID | DAD_Counter | secret_key)
+  //  with the 64 bit network prefix in network byte order,
+  //  the interface name and network id in UTF-8 and the
+  //  counter as a single byte.
+  class func stable_privacy_iid(_ prefix: IPAddress, _ interface: String, _ network_id: String,
+                                _ dad_counter: UInt8, _ secret_key: [UInt8]) -> UInt64 {
+    var data = [UInt8]();
+    let network = prefix.network().address.hi;
+    for shift in stride(from: 56, through: 0, by: -8) {
+      data.append(UInt8(truncatingIfNeeded: network >> UInt64(shift)));
+    }
+    data.append(contentsOf: Array(interface.utf8));
+    data.append(contentsOf: Array(network_id.utf8));
+    data.append(dad_counter);
+    data.append(contentsOf: secret_key);
+    var ret: UInt64 = 0;
+    for byte in Sha256.digest(data).suffix(8) {
+      ret = ret << 8 | UInt64(byte);
+    }
+    return ret;
+  }
+
+  //  The stable privacy address of RFC 7217 for +interface+ in
+  //  the network +prefix+, see Ipv6.from_prefix. +network_id+
+  //  optionally names the network (e.g. the SSID),
+  //  +dad_counter+ is incremented by the caller on each
+  //  duplicate address detection failure and +secret_key+
+  //  should be at least 128 random bits kept by the host.
+  //  A reserved identifier is skipped by incrementing the
+  //  counter as the RFC says, nil once the counter runs out.
+  public class func stable_privacy(_ prefix: IPAddress, interface: String, network_id: String = "",
+                                   dad_counter: UInt8 = 0, secret_key: [UInt8]) -> IPAddress? {
+    if (!prefix.is_ipv6() || prefix.prefix.num > 64) {
+      return nil;
+    }
+    var counter = dad_counter;
+    while (true) {
+      let iid = Ipv6.stable_privacy_iid(prefix, interface, network_id, counter, secret_key);
+      if (!Ipv6.is_reserved_interface_id(iid)) {
+        return Ipv6.from_prefix(prefix, interface_id: iid);
+      }
+      if (counter == UInt8.max) {
+        return nil;
+      }
+      counter += 1;
+    }
+  }
+
+  //  A temporary address of RFC 8981 in the network +prefix+,
+  //  see Ipv6.from_prefix. The interface identifier is drawn
+  //  from +generator+ until it is not reserved, a new one is
+  //  expected whenever the preferred lifetime ends.
+  public class func temporary<T: RandomNumberGenerator>(_ prefix: IPAddress,
+                                                        using generator: inout T) -> IPAddress? {
+    if (!prefix.is_ipv6() || prefix.prefix.num > 64) {
+      return nil;
+    }
+    var iid: UInt64 = generator.next();
+    while (Ipv6.is_reserved_interface_id(iid)) {
+      iid = generator.next();
+    }
+    return Ipv6.from_prefix(prefix, interface_id: iid);
+  }
+
+  //  A temporary address drawn from the system generator
+  public class func temporary(_ prefix: IPAddress) -> IPAddress? {
+    var generator = SystemRandomNumberGenerator();
+    return Ipv6.temporary(prefix, using: &generator);
+  }
+}
@@ -0,0 +1,143 @@
+import Foundation
+
+//  The SOA and NS data of generated reverse zones. Names are
+//  made fully qualified, the contact may be given as mail
+//  address:
+//
+//    ReverseZoneOptions(primary: "ns1.example.com", contact: "hostmaster@example.com",
+//                       name_servers: ["ns1.example.com", "ns2.example.com"],
+//                       serial: 2024101601)
+//
+public struct ReverseZoneOptions: Equatable {
+  //  MNAME, the primary name server
+  public let primary: String;
+  //  RNAME, the mailbox of the zone administrator
+  public let contact: String;
+  //  The NS records, the primary alone if empty
+  public let name_servers: [String];
+  public let serial: UInt32;
+  public let ttl: UInt32;
+  public let refresh: UInt32;
+  public let retry: UInt32;
+  public let expire: UInt32;
+  public let minimum: UInt32;
+
+  public init(primary: String, contact: String, name_servers: [String] = [],
+              serial: UInt32 = 1, ttl: UInt32 = 3600, refresh: UInt32 = 3600,
+              retry: UInt32 = 900, expire: UInt32 = 604800, minimum: UInt32 = 3600) {
+    self.primary = primary;
+    self.contact = contact;
+    self.name_servers = name_servers;
+    self.serial = serial;
+    self.ttl = ttl;
+    self.refresh = refresh;
+    self.retry = retry;
+    self.expire = expire;
+    self.minimum = minimum;
+  }
+
+  static func fqdn(_ name: String) -> String {
+    if (name.hasSuffix(".")) {
+      return name;
+    }
+    return "\(name).";
+  }
+
+  //  The contact as domain name, "hostmaster@example.com"
+  //  becomes "hostmaster.example.com." with the dots of the
+  //  local part escaped.
+  func rname() -> String {
+    let at = self.contact.lastIndex(of: "@");
+    if (at == nil) {
+      return ReverseZoneOptions.fqdn(self.contact);
+    }
+    let local = self.contact[..<at!].replacingOccurrences(of: ".", with: "\\.");
+    return ReverseZoneOptions.fqdn("\(local).\(self.contact[self.contact.index(after: at!)...])");
+  }
+}
+
+//  A PTR record, +name+ is the owner relative to the origin
+//  of its zone, "@" for the origin itself.
+public struct PtrRecord: Equatable {
+  public let name: String;
+  public let address: IPAddress;
+  public let host: String;
+}
+
+//  One reverse zone of IPAddress.reverse_zones, the network
+//  it covers and its PTR records sorted by address.
+public struct ReverseZone: Equatable {
+  public let network: IPAddress;
+  //  The fully qualified zone name, "2.0.192.in-addr.arpa."
+  public let origin: String;
+  public let records: [PtrRecord];
+
+  //  The RFC 1035 master file of the zone, the fields of the
+  //  records are separated by tabs:
+  //
+  //    $ORIGIN 2.0.192.in-addr.arpa.
+  //    $TTL 3600
+  //    @  IN  SOA  ns1.example.com. hostmaster.example.com. 1 3600 900 604800 3600
+  //    @  IN  NS   ns1.example.com.
+  //    1  IN  PTR  router.example.com.
+  //
+  public func to_master_file(_ options: ReverseZoneOptions) -> String {
+    var lines = [
+      "$ORIGIN \(self.origin)",
+      "$TTL \(options.ttl)",
+      "@\tIN\tSOA\t\(ReverseZoneOptions.fqdn(options.primary)) \(options.rname()) " +
+        "\(options.serial) \(options.refresh) \(options.retry) \(options.expire) \(options.minimum)",
+    ];
+    let name_servers = options.name_servers.isEmpty ? [options.primary] : options.name_servers;
+    for ns in name_servers {
+      lines.append("@\tIN\tNS\t\(ReverseZoneOptions.fqdn(ns))");
+    }
+    for record in self.records {
+      lines.append("\(record.name)\tIN\tPTR\t\(ReverseZoneOptions.fqdn(record.host))");
+    }
+    return lines.joined(separator: "\n") + "\n";
+  }
+}
+
+//  Reverse zones with PTR records for the hosts of a network:
+//
+//    let hosts = [IPAddress("192.0.2.1")!: "router.example.com"]
+//
+//    IPAddress("192.0.2.0/24")!.reverse_zones(hosts).map({ $0.origin })
+//      // => ["2.0.192.in-addr.arpa."]
+//    IPAddress("192.0.2.0/23")!.reverse_zones(hosts).map({ $0.records.count })
+//      // => [0, 1], a /23 is split into two /24 zones
+//
+extension IPAddress {
+  //  The zones of dns_networks with the PTR records of the
+  //  +hosts+ inside this network, hosts of other networks
+  //  are left out. The prefix and the zone of a host address
+  //  are ignored.
+  public func reverse_zones(_ hosts: [IPAddress: String]) -> [ReverseZone] {
+    var addresses = [(IPAddress, String)]();
+    for (ip, host) in hosts {
+      let address = IPAddress(ip_bits: ip.ip_bits, address: ip.address,
+                              prefix: ip.prefix.from(ip.ip_bits.bits)!);
+      if (self.with_zone(nil).includes(address)) {
+        addresses.append((address, host));
+      }
+    }
+    addresses.sort(by: { $0.0 < $1.0 || ($0.0 == $1.0 && $0.1 < $1.1) });
+    var ret = [ReverseZone]();
+    for net in self.with_zone(nil).dns_networks() {
+      let origin = net.dns_reverse();
+      var records = [PtrRecord]();
+      for (address, host) in addresses where net.includes(address) {
+        var name = address.dns_reverse();
+        if (name == origin) {
+          name = "@";
+        } else {
+          name = String(name.dropLast(origin.count + 1));
+        }
+        records.append(PtrRecord(name: name, address: address, host: host));
+      }
+      ret.append(ReverseZone(network: net, origin: "\(origin).", records: records));
+    }
+    return ret;
+  }
+}
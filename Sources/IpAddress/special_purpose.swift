@@ -0,0 +1,136 @@
+//  An entry of the IANA IPv4 and IPv6 Special-Purpose Address
+//  Registries (RFC 6890), with the flags of the registry:
+//
+//    source:               valid as source address
+//    destination:          valid as destination address
+//    forwardable:          routers may forward packets with it
+//    globally_reachable:   reachable beyond the local domain,
+//                          nil where the registry says N/A
+//    reserved_by_protocol: reserved by a protocol specification
+//
+//    let entry = IPAddress("100.64.1.1")!.special_purpose()!
+//
+//    entry.name
+//      // => "Shared Address Space"
+//    entry.forwardable
+//      // => true
+//    entry.globally_reachable
+//      // => false
+//
+//  The blocks not listed in the registries are ordinary
+//  unicast space, and multicast, which has its own registries.
+//
+public struct SpecialPurpose {
+  public let network: IPAddress;
+  public let name: String;
+  public let rfc: String;
+  public let source: Bool;
+  public let destination: Bool;
+  public let forwardable: Bool;
+  public let globally_reachable: Bool?;
+  public let reserved_by_protocol: Bool;
+
+  init(_ network: String, _ name: String, _ rfc: String, _ source: Bool, _ destination: Bool,
+       _ forwardable: Bool, _ globally_reachable: Bool?, _ reserved_by_protocol: Bool) {
+    self.network = try! IPAddress.parse(network);
+    self.name = name;
+    self.rfc = rfc;
+    self.source = source;
+    self.destination = destination;
+    self.forwardable = forwardable;
+    self.globally_reachable = globally_reachable;
+    self.reserved_by_protocol = reserved_by_protocol;
+  }
+
+  //  The IPv4 entries in the order of the registry
+  public static let ipv4_registry = [
+    SpecialPurpose("0.0.0.0/8", "This network", "RFC 791", true, false, false, false, true),
+    SpecialPurpose("0.0.0.0/32", "This host on this network", "RFC 1122", true, false, false, false, true),
+    SpecialPurpose("10.0.0.0/8", "Private-Use", "RFC 1918", true, true, true, false, false),
+    SpecialPurpose("100.64.0.0/10", "Shared Address Space", "RFC 6598", true, true, true, false, false),
+    SpecialPurpose("127.0.0.0/8", "Loopback", "RFC 1122", false, false, false, false, true),
+    SpecialPurpose("169.254.0.0/16", "Link Local", "RFC 3927", true, true, false, false, true),
+    SpecialPurpose("172.16.0.0/12", "Private-Use", "RFC 1918", true, true, true, false, false),
+    SpecialPurpose("192.0.0.0/24", "IETF Protocol Assignments", "RFC 6890", false, false, false, false, false),
+    SpecialPurpose("192.0.0.0/29", "IPv4 Service Continuity Prefix", "RFC 7335", true, true, true, false, false),
+    SpecialPurpose("192.0.0.8/32", "IPv4 dummy address", "RFC 7600", true, false, false, false, false),
+    SpecialPurpose("192.0.0.9/32", "Port Control Protocol Anycast", "RFC 7723", true, true, true, true, false),
+    SpecialPurpose("192.0.0.10/32", "Traversal Using Relays around NAT Anycast", "RFC 8155",
+                   true, true, true, true, false),
+    SpecialPurpose("192.0.0.170/32", "NAT64/DNS64 Discovery", "RFC 8880", false, false, false, false, true),
+    SpecialPurpose("192.0.0.171/32", "NAT64/DNS64 Discovery", "RFC 8880", false, false, false, false, true),
+    SpecialPurpose("192.0.2.0/24", "Documentation (TEST-NET-1)", "RFC 5737", false, false, false, false, false),
+    SpecialPurpose("192.31.196.0/24", "AS112-v4", "RFC 7535", true, true, true, true, false),
+    SpecialPurpose("192.52.193.0/24", "AMT", "RFC 7450", true, true, true, true, false),
+    SpecialPurpose("192.88.99.0/24", "Deprecated (6to4 Relay Anycast)", "RFC 7526", false, false, false, nil, false),
+    SpecialPurpose("192.168.0.0/16", "Private-Use", "RFC 1918", true, true, true, false, false),
+    SpecialPurpose("192.175.48.0/24", "Direct Delegation AS112 Service", "RFC 7534", true, true, true, true, false),
+    SpecialPurpose("198.18.0.0/15", "Benchmarking", "RFC 2544", true, true, true, false, false),
+    SpecialPurpose("198.51.100.0/24", "Documentation (TEST-NET-2)", "RFC 5737", false, false, false, false, false),
+    SpecialPurpose("203.0.113.0/24", "Documentation (TEST-NET-3)", "RFC 5737", false, false, false, false, false),
+    SpecialPurpose("240.0.0.0/4", "Reserved", "RFC 1112", false, false, false, false, true),
+    SpecialPurpose("255.255.255.255/32", "Limited Broadcast", "RFC 919", false, true, false, false, true),
+  ];
+
+  //  The IPv6 entries in the order of the registry
+  public static let ipv6_registry = [
+    SpecialPurpose("::1/128", "Loopback Address", "RFC 4291", false, false, false, false, true),
+    SpecialPurpose("::/128", "Unspecified Address", "RFC 4291", true, false, false, false, true),
+    SpecialPurpose("::ffff:0:0/96", "IPv4-mapped Address", "RFC 4291", false, false, false, false, true),
+    SpecialPurpose("64:ff9b::/96", "IPv4-IPv6 Translat.", "RFC 6052", true, true, true, true, false),
+    SpecialPurpose("64:ff9b:1::/48", "IPv4-IPv6 Translat.", "RFC 8215", true, true, true, false, false),
+    SpecialPurpose("100::/64", "Discard-Only Address Block", "RFC 6666", true, true, true, false, false),
+    SpecialPurpose("2001::/23", "IETF Protocol Assignments", "RFC 2928", false, false, false, false, false),
+    SpecialPurpose("2001::/32", "TEREDO", "RFC 4380", true, true, true, nil, false),
+    SpecialPurpose("2001:1::1/128", "Port Control Protocol Anycast", "RFC 7723", true, true, true, true, false),
+    SpecialPurpose("2001:1::2/128", "Traversal Using Relays around NAT Anycast", "RFC 8155",
+                   true, true, true, true, false),
+    SpecialPurpose("2001:2::/48", "Benchmarking", "RFC 5180", true, true, true, false, false),
+    SpecialPurpose("2001:3::/32", "AMT", "RFC 7450", true, true, true, true, false),
+    SpecialPurpose("2001:4:112::/48", "AS112-v6", "RFC 7535", true, true, true, true, false),
+    SpecialPurpose("2001:10::/28", "Deprecated (previously ORCHID)", "RFC 4843", false, false, false, nil, false),
+    SpecialPurpose("2001:20::/28", "ORCHIDv2", "RFC 7343", true, true, true, true, false),
+    SpecialPurpose("2001:30::/28", "Drone Remote ID Protocol Entity Tags (DETs) Prefix", "RFC 9374",
+                   true, true, true, true, false),
+    SpecialPurpose("2001:db8::/32", "Documentation", "RFC 3849", false, false, false, false, false),
+    SpecialPurpose("2002::/16", "6to4", "RFC 3056", true, true, true, nil, false),
+    SpecialPurpose("2620:4f:8000::/48", "Direct Delegation AS112 Service", "RFC 7534", true, true, true, true, false),
+    SpecialPurpose("3fff::/20", "Documentation", "RFC 9637", false, false, false, false, false),
+    SpecialPurpose("5f00::/16", "Segment Routing (SRv6) SIDs", "RFC 9602", true, true, true, false, false),
+    SpecialPurpose("fc00::/7", "Unique-Local", "RFC 4193", true, true, true, false, false),
+    SpecialPurpose("fe80::/10", "Link-Local Unicast", "RFC 4291", true, true, false, false, true),
+  ];
+
+  static let registry: IPPrefixMap<SpecialPurpose> = {
+    var ret = IPPrefixMap<SpecialPurpose>();
+    for entry in SpecialPurpose.ipv4_registry + SpecialPurpose.ipv6_registry {
+      ret[entry.network] = entry;
+    }
+    return ret;
+  }();
+
+  //  All the entries which include +ip+, the least specific
+  //  first.
+  public static func lookup(_ ip: IPAddress) -> [SpecialPurpose] {
+    return SpecialPurpose.registry.covering(ip).map({ $0.value });
+  }
+
+  static let link_local = IPAddress.to_ipaddress_vec(["169.254.0.0/16", "fe80::/10"])!;
+  static let documentation = IPAddress.to_ipaddress_vec(["192.0.2.0/24", "198.51.100.0/24", "203.0.113.0/24",
+                                                         "2001:db8::/32", "3fff::/20"])!;
+  static let shared = IPAddress.to_ipaddress_vec(["100.64.0.0/10"])!;
+  static let benchmarking = IPAddress.to_ipaddress_vec(["198.18.0.0/15", "2001:2::/48"])!;
+  static let unique_local = IPAddress.to_ipaddress_vec(["fc00::/7"])!;
+  static let multicast = IPAddress.to_ipaddress_vec(["224.0.0.0/4", "ff00::/8"])!;
+  //  The Reserved block of IPv4 and the blocks "Reserved by
+  //  IETF" of the IANA IPv6 Address Space registry
+  static let reserved = IPAddress.to_ipaddress_vec(["240.0.0.0/4",
+                                                    "::/8", "100::/8", "200::/7", "400::/6", "800::/5",
+                                                    "1000::/4", "4000::/3", "6000::/3", "8000::/3",
+                                                    "a000::/3", "c000::/3", "e000::/4", "f000::/5",
+                                                    "f800::/6", "fe00::/9", "fec0::/10"])!;
+
+  static func includes(_ networks: [IPAddress], _ ip: IPAddress) -> Bool {
+    return networks.firstIndex(where: { $0.includes(ip.with_zone(nil)) }) != nil;
+  }
+}
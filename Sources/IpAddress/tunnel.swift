@@ -0,0 +1,147 @@
+//  The parts of a Teredo address (RFC 4380),
+//  2001:0:<server>:<flags>:<port>:<client> where the port and
+//  the client address are stored with all bits inverted.
+//
+//    let teredo = IPAddress("2001:0:4136:e378:8000:63bf:3fff:fdd2")!.teredo()!
+//
+//    teredo.server.to_s()
+//      // => "65.54.227.120"
+//    teredo.client.to_s()
+//      // => "192.0.2.45"
+//    teredo.port
+//      // => 40000
+//    teredo.is_cone()
+//      // => true
+//
+public struct Teredo: Equatable {
+  public let server: IPAddress;
+  public let flags: UInt16;
+  //  The external UDP port of the client, not obfuscated
+  public let port: UInt16;
+  //  The external IPv4 address of the client, not obfuscated
+  public let client: IPAddress;
+
+  public init(server: IPAddress, flags: UInt16, port: UInt16, client: IPAddress) {
+    self.server = server;
+    self.flags = flags;
+    self.port = port;
+    self.client = client;
+  }
+
+  //  The C flag, the client is behind a cone NAT
+  public func is_cone() -> Bool {
+    return self.flags & 0x8000 != 0;
+  }
+
+  //  The Teredo address of these parts, nil if the server or
+  //  the client is not IPv4
+  public func to_ipaddress() -> IPAddress? {
+    if (!self.server.is_ipv4() || !self.client.is_ipv4()) {
+      return nil;
+    }
+    let hi = UInt64(0x20010000) << 32 | UInt64(self.server.address.u32);
+    let lo = UInt64(self.flags) << 48 | UInt64(~self.port) << 32 | UInt64(~self.client.address.u32);
+    return IPAddress(ip_bits: IpBits.v6(), address: UInt128(hi: hi, lo: lo), prefix: Prefix128.create(128)!);
+  }
+}
+
+//  The IPv6 transition addresses which carry an IPv4 address:
+//  6to4 (RFC 3056), Teredo (RFC 4380) and the ISATAP interface
+//  identifiers (RFC 5214).
+//
+//    IPAddress("192.0.2.1")!.to_6to4()!.to_string()
+//      // => "2002:c000:201::/48"
+//    IPAddress("2002:c000:201:1::1")!.six_to_four_ipv4()!.to_s()
+//      // => "192.0.2.1"
+//    IPAddress("fe80::5efe:c0a8:101")!.isatap_ipv4()!.to_s()
+//      // => "192.168.1.1"
+//
+extension IPAddress {
+  static let six_to_four_prefix = try! IPAddress.parse("2002::/16");
+  static let teredo_prefix = try! IPAddress.parse("2001::/32");
+
+  //  The 6to4 network 2002:<ipv4>::/48 of an IPv4 address,
+  //  nil for IPv6. The prefix of an IPv4 network is carried
+  //  over, 192.0.2.0/24 gives 2002:c000:200::/40.
+  public func to_6to4() -> IPAddress? {
+    if (!self.is_ipv4()) {
+      return nil;
+    }
+    let address = UInt128(UInt64(0x2002)) << 112 | UInt128(self.address.u32) << 80;
+    return IPAddress(ip_bits: IpBits.v6(), address: address, prefix: Prefix128.create(self.prefix.num + 16)!);
+  }
+
+  public func is_6to4() -> Bool {
+    return IPAddress.six_to_four_prefix.includes(self);
+  }
+
+  //  The IPv4 address embedded in a 6to4 address, nil outside
+  //  of 2002::/16. Prefixes up to /48 are translated back to
+  //  the IPv4 prefix, longer ones give the IPv4 host.
+  public func six_to_four_ipv4() -> IPAddress? {
+    if (!self.is_6to4()) {
+      return nil;
+    }
+    return Ipv4.from_u32((self.address >> 80).u32, min(self.prefix.num, 48) - 16);
+  }
+
+  //  The Teredo address of this IPv4 client address behind the
+  //  NAT port +port+, served by the IPv4 +server+. nil if one
+  //  of the addresses is not IPv4.
+  //
+  //    IPAddress("192.0.2.45")!.to_teredo(IPAddress("65.54.227.120")!, 40000, 0x8000)!.to_s()
+  //      // => "2001:0:4136:e378:8000:63bf:3fff:fdd2"
+  //
+  public func to_teredo(_ server: IPAddress, _ port: UInt16, _ flags: UInt16 = 0) -> IPAddress? {
+    return Teredo(server: server, flags: flags, port: port, client: self).to_ipaddress();
+  }
+
+  public func is_teredo() -> Bool {
+    return IPAddress.teredo_prefix.includes(self);
+  }
+
+  //  The parts of a Teredo address, nil outside of 2001::/32
+  public func teredo() -> Teredo? {
+    if (!self.is_teredo()) {
+      return nil;
+    }
+    let lo = self.address.lo;
+    return Teredo(server: Ipv4.from_u32(UInt32(truncatingIfNeeded: self.address.hi), 32)!,
+                  flags: UInt16(truncatingIfNeeded: lo >> 48),
+                  port: ~UInt16(truncatingIfNeeded: lo >> 32),
+                  client: Ipv4.from_u32(~UInt32(truncatingIfNeeded: lo), 32)!);
+  }
+
+  //  The ISATAP address of this IPv4 address in the network
+  //  +prefix+, see Ipv6.from_prefix. The interface identifier
+  //  is 0000:5efe:<ipv4>, or 0200:5efe:<ipv4> with the U/L bit
+  //  set for globally reachable IPv4 addresses.
+  //
+  //    IPAddress("192.168.1.1")!.to_isatap(IPAddress("fe80::/64")!)!.to_string()
+  //      // => "fe80::5efe:c0a8:101/64"
+  //
+  public func to_isatap(_ prefix: IPAddress) -> IPAddress? {
+    if (!self.is_ipv4()) {
+      return nil;
+    }
+    var iid = UInt64(0x5efe) << 32 | UInt64(self.address.u32);
+    if (self.is_global()) {
+      iid = iid | 0x0200000000000000;
+    }
+    return Ipv6.from_prefix(prefix, interface_id: iid);
+  }
+
+  //  True for an IPv6 address with an ISATAP interface
+  //  identifier, [000000ug]00:5efe:<ipv4>
+  public func is_isatap() -> Bool {
+    return self.is_ipv6() && (self.address.lo >> 32) & 0xfcffffff == 0x5efe;
+  }
+
+  //  The IPv4 address in an ISATAP interface identifier
+  public func isatap_ipv4() -> IPAddress? {
+    if (!self.is_isatap()) {
+      return nil;
+    }
+    return Ipv4.from_u32(self.address.u32, 32);
+  }
+}
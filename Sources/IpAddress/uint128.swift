@@ -0,0 +1,217 @@
+import BigInt
+
+//  Fixed width 128 bit unsigned integer made of two 64 bit words.
+//
+//  It is the storage of every IPAddress and Prefix: IPv4 values
+//  use only the low 32 bits, IPv6 values the full width. All the
+//  operations are plain value arithmetic and never allocate, BigUInt
+//  is only used at the edges of the API (from_int, host_address).
+//
+//    let num = UInt128(hi: 0x20010db8_00000000, lo: 1)
+//
+//    num.to_string(16)
+//      // => "20010db8000000000000000000000001"
+//
+public struct UInt128: Hashable, Comparable, CustomStringConvertible, ExpressibleByIntegerLiteral {
+  public let hi: UInt64;
+  public let lo: UInt64;
+
+  public static let zero = UInt128(hi: 0, lo: 0);
+  public static let one = UInt128(hi: 0, lo: 1);
+  public static let max = UInt128(hi: UInt64.max, lo: UInt64.max);
+
+  public init(hi: UInt64, lo: UInt64) {
+    self.hi = hi;
+    self.lo = lo;
+  }
+
+  public init(integerLiteral num: UInt64) {
+    self.init(hi: 0, lo: num);
+  }
+
+  public init(_ num: UInt64) {
+    self.init(hi: 0, lo: num);
+  }
+
+  public init(_ num: UInt32) {
+    self.init(hi: 0, lo: UInt64(num));
+  }
+
+  public init(_ num: UInt) {
+    self.init(hi: 0, lo: UInt64(num));
+  }
+
+  //  Converts a BigUInt, returns nil if it does not fit
+  //  into 128 bits.
+  public init?(_ big: BigUInt) {
+    if ((big >> 128) != BigUInt(0)) {
+      return nil;
+    }
+    self.init(hi: UInt64(truncatingIfNeeded: big >> 64),
+              lo: UInt64(truncatingIfNeeded: big));
+  }
+
+  public var big: BigUInt {
+    return (BigUInt(self.hi) << 64) + BigUInt(self.lo);
+  }
+
+  public var description: String {
+    return self.to_string(10);
+  }
+
+  public var is_zero: Bool {
+    return self.hi == 0 && self.lo == 0;
+  }
+
+  //  The low 32 bits, this is the value of an IPv4 address.
+  public var u32: UInt32 {
+    return UInt32(truncatingIfNeeded: self.lo);
+  }
+
+  public var leadingZeroBitCount: Int {
+    if (self.hi != 0) {
+      return self.hi.leadingZeroBitCount;
+    }
+    return 64 + self.lo.leadingZeroBitCount;
+  }
+
+  public var trailingZeroBitCount: Int {
+    if (self.lo != 0) {
+      return self.lo.trailingZeroBitCount;
+    }
+    return 64 + self.hi.trailingZeroBitCount;
+  }
+
+  public var nonzeroBitCount: Int {
+    return self.hi.nonzeroBitCount + self.lo.nonzeroBitCount;
+  }
+
+  //  Returns a value with the lowest +bits+ bits set,
+  //  mask(0) is zero and mask(128) is max.
+  public static func mask(_ bits: Int) -> UInt128 {
+    if (bits <= 0) {
+      return UInt128.zero;
+    }
+    if (bits >= 128) {
+      return UInt128.max;
+    }
+    return (UInt128.one << bits) - UInt128.one;
+  }
+
+  public func bit(_ idx: Int) -> Bool {
+    return !((self >> idx) & UInt128.one).is_zero;
+  }
+
+  public func addingReportingOverflow(_ oth: UInt128) -> (partialValue: UInt128, overflow: Bool) {
+    let (lo, c_lo) = self.lo.addingReportingOverflow(oth.lo);
+    let (hi1, c_hi1) = self.hi.addingReportingOverflow(oth.hi);
+    let (hi, c_hi2) = hi1.addingReportingOverflow(c_lo ? 1 : 0);
+    return (UInt128(hi: hi, lo: lo), c_hi1 || c_hi2);
+  }
+
+  public func subtractingReportingOverflow(_ oth: UInt128) -> (partialValue: UInt128, overflow: Bool) {
+    let (lo, b_lo) = self.lo.subtractingReportingOverflow(oth.lo);
+    let (hi1, b_hi1) = self.hi.subtractingReportingOverflow(oth.hi);
+    let (hi, b_hi2) = hi1.subtractingReportingOverflow(b_lo ? 1 : 0);
+    return (UInt128(hi: hi, lo: lo), b_hi1 || b_hi2);
+  }
+
+  public func quotientAndRemainder(dividingBy div: UInt64) -> (quotient: UInt128, remainder: UInt64) {
+    let (q_hi, r_hi) = self.hi.quotientAndRemainder(dividingBy: div);
+    let (q_lo, r_lo) = div.dividingFullWidth((high: r_hi, low: self.lo));
+    return (UInt128(hi: q_hi, lo: q_lo), r_lo);
+  }
+
+  public func to_string(_ radix: Int) -> String {
+    if (self.is_zero) {
+      return "0";
+    }
+    var digits = [Character]();
+    var num = self;
+    while (!num.is_zero) {
+      let (q, r) = num.quotientAndRemainder(dividingBy: UInt64(radix));
+      digits.append(Character(String(r, radix: radix)));
+      num = q;
+    }
+    return String(digits.reversed());
+  }
+
+  public static func ==(lhs: UInt128, rhs: UInt128) -> Bool {
+    return lhs.hi == rhs.hi && lhs.lo == rhs.lo;
+  }
+
+  public static func <(lhs: UInt128, rhs: UInt128) -> Bool {
+    if (lhs.hi != rhs.hi) {
+      return lhs.hi < rhs.hi;
+    }
+    return lhs.lo < rhs.lo;
+  }
+
+  //  Wrapping addition, like &+ on the builtin integers.
+  public static func &+(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    return lhs.addingReportingOverflow(rhs).partialValue;
+  }
+
+  //  Wrapping subtraction, like &- on the builtin integers.
+  public static func &-(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    return lhs.subtractingReportingOverflow(rhs).partialValue;
+  }
+
+  public static func +(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    let (ret, overflow) = lhs.addingReportingOverflow(rhs);
+    precondition(!overflow, "UInt128 addition overflow");
+    return ret;
+  }
+
+  public static func -(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    let (ret, overflow) = lhs.subtractingReportingOverflow(rhs);
+    precondition(!overflow, "UInt128 subtraction underflow");
+    return ret;
+  }
+
+  public static func &(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    return UInt128(hi: lhs.hi & rhs.hi, lo: lhs.lo & rhs.lo);
+  }
+
+  public static func |(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    return UInt128(hi: lhs.hi | rhs.hi, lo: lhs.lo | rhs.lo);
+  }
+
+  public static func ^(lhs: UInt128, rhs: UInt128) -> UInt128 {
+    return UInt128(hi: lhs.hi ^ rhs.hi, lo: lhs.lo ^ rhs.lo);
+  }
+
+  public static prefix func ~(val: UInt128) -> UInt128 {
+    return UInt128(hi: ~val.hi, lo: ~val.lo);
+  }
+
+  //  Shifts out of range yield zero, as the smart shifts
+  //  of the builtin integers do.
+  public static func <<(lhs: UInt128, rhs: Int) -> UInt128 {
+    if (rhs <= 0) {
+      return rhs == 0 ? lhs : lhs >> -rhs;
+    }
+    if (rhs >= 128) {
+      return UInt128.zero;
+    }
+    if (rhs >= 64) {
+      return UInt128(hi: lhs.lo << UInt64(rhs - 64), lo: 0);
+    }
+    return UInt128(hi: (lhs.hi << UInt64(rhs)) | (lhs.lo >> UInt64(64 - rhs)),
+                   lo: lhs.lo << UInt64(rhs));
+  }
+
+  public static func >>(lhs: UInt128, rhs: Int) -> UInt128 {
+    if (rhs <= 0) {
+      return rhs == 0 ? lhs : lhs << -rhs;
+    }
+    if (rhs >= 128) {
+      return UInt128.zero;
+    }
+    if (rhs >= 64) {
+      return UInt128(hi: 0, lo: lhs.hi >> UInt64(rhs - 64));
+    }
+    return UInt128(hi: lhs.hi >> UInt64(rhs),
+                   lo: (lhs.lo >> UInt64(rhs)) | (lhs.hi << UInt64(64 - rhs)));
+  }
+}
@@ -0,0 +1,105 @@
+import XCTest
+import Foundation
+@testable import IpAddress
+
+class CodableTests : XCTestCase {
+  
+  func encode<T: Encodable>(_ val: T, _ coding: IPAddressCoding?) -> String {
+    let encoder = JSONEncoder();
+    encoder.outputFormatting = .sortedKeys;
+    if (coding != nil) {
+      encoder.userInfo[IPAddressCoding.user_info_key] = coding!;
+    }
+    return String(data: try! encoder.encode([val]), encoding: .utf8)!;
+  }
+  
+  func decode<T: Decodable>(_ type: T.Type, _ json: String, _ coding: IPAddressCoding?) throws -> T {
+    let decoder = JSONDecoder();
+    if (coding != nil) {
+      decoder.userInfo[IPAddressCoding.user_info_key] = coding!;
+    }
+    return try decoder.decode([T].self, from: json.data(using: .utf8)!)[0];
+  }
+  
+  func test_ipaddress_cidr() {
+    let ip = try! IPAddress.parse("10.0.0.1/24");
+    XCTAssertEqual("[\"10.0.0.1\\/24\"]", encode(ip, nil));
+    XCTAssertEqual("[\"10.0.0.1\\/24\"]", encode(ip, IPAddressCoding.cidr));
+    XCTAssertEqual(ip, try! decode(IPAddress.self, "[\"10.0.0.1/24\"]", nil));
+    let ip6 = try! IPAddress.parse("fe80::1%eth0/64");
+    XCTAssertEqual(ip6, try! decode(IPAddress.self, encode(ip6, nil), nil));
+  }
+  
+  func test_ipaddress_address() {
+    let ip = try! IPAddress.parse("2001:db8::1/64");
+    XCTAssertEqual("[\"2001:db8::1\"]", encode(ip, IPAddressCoding.address));
+    XCTAssertEqual(try! IPAddress.parse("2001:db8::1"),
+                   try! decode(IPAddress.self, "[\"2001:db8::1\"]", IPAddressCoding.address));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[\"2001:db8::1/64\"]", IPAddressCoding.address));
+  }
+  
+  func test_ipaddress_integer() {
+    let ip = try! IPAddress.parse("10.0.0.1/24");
+    XCTAssertEqual("[167772161]", encode(ip, IPAddressCoding.integer));
+    XCTAssertEqual(try! IPAddress.parse("10.0.0.1"),
+                   try! decode(IPAddress.self, "[167772161]", IPAddressCoding.integer));
+    let ip6 = try! IPAddress.parse("2001:db8::1");
+    XCTAssertEqual("[\"42540766411282592856903984951653826561\"]", encode(ip6, IPAddressCoding.integer));
+    XCTAssertEqual(ip6, try! decode(IPAddress.self, "[\"42540766411282592856903984951653826561\"]",
+                                    IPAddressCoding.integer));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[4294967296]", IPAddressCoding.integer));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[\"-1\"]", IPAddressCoding.integer));
+    let encoder = JSONEncoder();
+    encoder.userInfo[IPAddressCoding.user_info_key] = IPAddressCoding.integer;
+    XCTAssertThrowsError(try encoder.encode([try! IPAddress.parse("fe80::1%eth0")]));
+  }
+  
+  func test_ipaddress_object() {
+    let ip = try! IPAddress.parse("10.0.0.1/24");
+    XCTAssertEqual("[{\"address\":\"10.0.0.1\",\"prefix\":24}]", encode(ip, IPAddressCoding.object));
+    XCTAssertEqual(ip, try! decode(IPAddress.self, "[{\"address\":\"10.0.0.1\",\"prefix\":24}]",
+                                   IPAddressCoding.object));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[{\"address\":\"10.0.0.1\",\"prefix\":33}]",
+                                    IPAddressCoding.object));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[{\"address\":\"10.0.0.1\"}]", IPAddressCoding.object));
+  }
+  
+  func test_ipaddress_error() {
+    XCTAssertThrowsError(try decode(IPAddress.self, "[\"10.0.0.256/24\"]", nil)) { err in
+      guard case DecodingError.dataCorrupted(let ctx) = err else {
+        return XCTFail("\(err)");
+      }
+      XCTAssertEqual("octet out of range '256' at column 8", ctx.debugDescription);
+      XCTAssertEqual(IPAddressParseError.octet_out_of_range("256", 7),
+                     ctx.underlyingError as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try decode(IPAddress.self, "[24]", nil));
+    XCTAssertThrowsError(try decode(IPAddress.self, "[\"10.0.0.1/24\"]", IPAddressCoding.object));
+  }
+  
+  func test_prefix() {
+    let prefix = Prefix32.create(24)!;
+    XCTAssertEqual("[\"255.255.255.0\"]", encode(prefix, nil));
+    XCTAssertEqual("[4294967040]", encode(prefix, IPAddressCoding.integer));
+    XCTAssertEqual("[{\"prefix\":24,\"version\":4}]", encode(prefix, IPAddressCoding.object));
+    for coding in [IPAddressCoding.cidr, IPAddressCoding.address, IPAddressCoding.integer, IPAddressCoding.object] {
+      for prefix in [Prefix32.create(24)!, Prefix128.create(64)!, Prefix128.create(0)!] {
+        XCTAssertEqual(prefix, try! decode(Prefix.self, encode(prefix, coding), coding));
+      }
+    }
+    XCTAssertThrowsError(try decode(Prefix.self, "[\"255.0.255.0\"]", nil));
+    XCTAssertThrowsError(try decode(Prefix.self, "[{\"prefix\":24,\"version\":5}]", IPAddressCoding.object));
+    XCTAssertThrowsError(try decode(Prefix.self, "[{\"prefix\":33,\"version\":4}]", IPAddressCoding.object));
+  }
+  
+  static var allTests : [(String, (CodableTests) -> () throws -> Void)] {
+    return [
+      ("test_ipaddress_cidr", test_ipaddress_cidr),
+      ("test_ipaddress_address", test_ipaddress_address),
+      ("test_ipaddress_integer", test_ipaddress_integer),
+      ("test_ipaddress_object", test_ipaddress_object),
+      ("test_ipaddress_error", test_ipaddress_error),
+      ("test_prefix", test_prefix),
+    ]
+  }
+}
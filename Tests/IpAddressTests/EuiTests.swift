@@ -0,0 +1,92 @@
+import XCTest
+@testable import IpAddress
+
+class EuiTests : XCTestCase {
+
+  func test_parse_eui48() {
+    let mac = Eui48(0x001a2b3c4d5e)!;
+    for str in ["00:1a:2b:3c:4d:5e", "00-1A-2B-3C-4D-5E", "001a.2b3c.4d5e", "001A2B3C4D5E", "0:1a:2b:3c:4d:5e"] {
+      XCTAssertEqual(mac, try! Eui48.parse(str), str);
+    }
+    XCTAssertEqual("00:1a:2b:3c:4d:5e", mac.to_s());
+    XCTAssertEqual("00:1a:2b:3c:4d:5e", mac.description);
+    XCTAssertEqual("00-1a-2b-3c-4d-5e", mac.to_s_dashed());
+    XCTAssertEqual("001a.2b3c.4d5e", mac.to_s_cisco());
+    XCTAssertEqual(mac, Eui48(mac.description));
+    XCTAssertNil(Eui48(0x1000000000000));
+    XCTAssertNil(Eui48("00:1a:2b:3c:4d"));
+    XCTAssertThrowsError(try Eui48.parse("00:1a:2b:3c:4d")) { err in
+      XCTAssertEqual(IPAddressParseError.wrong_eui_length("00:1a:2b:3c:4d", 0), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try Eui48.parse("00:1a:2b:3g:4d:5e")) { err in
+      XCTAssertEqual(IPAddressParseError.invalid_eui_group("3g", 9), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try Eui48.parse("00:1a:2b:\u{ff13}\u{ff43}:4d:5e")) { err in
+      XCTAssertEqual(IPAddressParseError.invalid_eui_group("\u{ff13}\u{ff43}", 9), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try Eui48.parse("00:1a:2b:3c:4d:5e0")) { err in
+      XCTAssertEqual(IPAddressParseError.invalid_eui_group("5e0", 15), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try Eui48.parse("00:1a-2b:3c:4d:5e"));
+    XCTAssertThrowsError(try Eui48.parse("001a.2b3c.4d5"));
+    XCTAssertThrowsError(try Eui48.parse("001a2b3c4d5e00"));
+    XCTAssertThrowsError(try Eui48.parse(""));
+    XCTAssertTrue(Eui48("01:00:5e:00:00:01")!.is_multicast());
+    XCTAssertFalse(mac.is_multicast());
+    XCTAssertTrue(Eui48("02:00:00:00:00:01")!.is_local());
+    XCTAssertFalse(mac.is_local());
+    XCTAssertTrue(Eui48("00:00:00:00:00:01")! < mac);
+  }
+
+  func test_parse_eui64() {
+    let eui = Eui64(0x001a2bfffe3c4d5e);
+    for str in ["00:1a:2b:ff:fe:3c:4d:5e", "00-1A-2B-FF-FE-3C-4D-5E", "001a.2bff.fe3c.4d5e", "001a2bfffe3c4d5e"] {
+      XCTAssertEqual(eui, try! Eui64.parse(str), str);
+    }
+    XCTAssertEqual("00:1a:2b:ff:fe:3c:4d:5e", eui.to_s());
+    XCTAssertEqual("00-1a-2b-ff-fe-3c-4d-5e", eui.to_s_dashed());
+    XCTAssertEqual("001a.2bff.fe3c.4d5e", eui.to_s_cisco());
+    XCTAssertEqual(eui, Eui64(eui.description));
+    XCTAssertNil(Eui64("00:1a:2b:3c:4d:5e"));
+    XCTAssertEqual("ff:ff:ff:ff:ff:ff:ff:ff", Eui64(UInt64.max).to_s());
+  }
+
+  func test_interface_id() {
+    let mac = Eui48("00:1a:2b:3c:4d:5e")!;
+    XCTAssertEqual("00:1a:2b:ff:fe:3c:4d:5e", mac.to_eui64().to_s());
+    XCTAssertEqual(0x021a2bfffe3c4d5e, mac.interface_id());
+    XCTAssertEqual(0x001a2bfffe3c4d5e, Eui48("02:1a:2b:3c:4d:5e")!.interface_id());
+    XCTAssertEqual(mac, mac.to_eui64().to_eui48());
+    XCTAssertNil(Eui64("00:1a:2b:3c:4d:5e:6f:70")!.to_eui48());
+    XCTAssertEqual(mac.to_eui64(), Eui64.from_interface_id(mac.interface_id()));
+  }
+
+  func test_slaac() {
+    let mac = Eui48("00:1a:2b:3c:4d:5e")!;
+    let ip = Ipv6.from_prefix(try! IPAddress.parse("2001:db8:1:2::/64"), mac: mac)!;
+    XCTAssertEqual("2001:db8:1:2:21a:2bff:fe3c:4d5e/64", ip.to_string());
+    XCTAssertEqual(mac, ip.mac());
+    XCTAssertEqual("00:1a:2b:ff:fe:3c:4d:5e", ip.eui64()!.to_s());
+    XCTAssertEqual(0x021a2bfffe3c4d5e, ip.interface_id());
+    XCTAssertEqual("2001:db8:1:2:21a:2bff:fe3c:4d5e/64",
+                   Ipv6.from_prefix(try! IPAddress.parse("2001:db8:1:2:ffff::1/64"), mac: mac)!.to_string());
+    XCTAssertEqual("fe80::21a:2bff:fe3c:4d5e%eth0/64",
+                   Ipv6.from_prefix(try! IPAddress.parse("fe80::%eth0/64"), mac: mac)!.to_string());
+    XCTAssertEqual("2001:db8::1/48", Ipv6.from_prefix(try! IPAddress.parse("2001:db8::/48"), interface_id: 1)!.to_string());
+    XCTAssertNil(Ipv6.from_prefix(try! IPAddress.parse("2001:db8::/80"), mac: mac));
+    XCTAssertNil(Ipv6.from_prefix(try! IPAddress.parse("10.0.0.0/8"), mac: mac));
+    XCTAssertNil(try! IPAddress.parse("2001:db8::1").mac());
+    XCTAssertNil(try! IPAddress.parse("10.0.0.1").mac());
+    XCTAssertNil(try! IPAddress.parse("10.0.0.1").interface_id());
+    XCTAssertEqual("02:00:00:00:00:01", try! IPAddress.parse("fe80::ff:fe00:1").mac()!.to_s());
+  }
+
+  static var allTests : [(String, (EuiTests) -> () throws -> Void)] {
+    return [
+      ("test_parse_eui48", test_parse_eui48),
+      ("test_parse_eui64", test_parse_eui64),
+      ("test_interface_id", test_interface_id),
+      ("test_slaac", test_slaac),
+    ]
+  }
+}
@@ -0,0 +1,104 @@
+import XCTest
+import BigInt
+@testable import IpAddress
+
+class IPAddressSetTests : XCTestCase {
+  
+  func set(_ strs: [String]) -> IPAddressSet {
+    return IPAddressSet(IPAddress.to_ipaddress_vec(strs)!);
+  }
+  
+  func nets(_ set: IPAddressSet) -> [String] {
+    return IPAddress.to_string_vec(set.networks());
+  }
+  
+  func test_initialize() {
+    XCTAssertEqual(["10.0.0.0/23", "10.0.2.0/24", "2001:db8::/32"],
+                   nets(set(["2001:db8::/32", "10.0.2.0/24", "10.0.1.0/24", "10.0.0.0/24", "10.0.0.128/25"])));
+    XCTAssertEqual("10.0.0.0/23, 10.0.2.0/24", set(["10.0.0.0/23", "10.0.2.0/24"]).description);
+    XCTAssertTrue(IPAddressSet().is_empty);
+    XCTAssertEqual(set(["10.0.0.0/24"]), IPAddressSet([try! IPRange.parse("10.0.0.0-10.0.0.255")]));
+  }
+  
+  func test_method_union() {
+    XCTAssertEqual(["10.0.0.0/22"], nets(set(["10.0.0.0/23"]).union(set(["10.0.2.0/23"]))));
+    XCTAssertEqual(["10.0.0.0/8", "::/0"], nets(set(["10.0.0.0/8"]).union(set(["::/0", "10.1.0.0/16"]))));
+    XCTAssertEqual(["0.0.0.0/0"], nets(set(["0.0.0.0/1"]).union(set(["128.0.0.0/1"]))));
+  }
+  
+  func test_method_intersection() {
+    XCTAssertEqual(["10.1.0.0/16"], nets(set(["10.0.0.0/8"]).intersection(set(["10.1.0.0/16", "11.0.0.0/8"]))));
+    XCTAssertEqual(["10.0.0.128/25", "10.0.1.0/25"],
+                   nets(set(["10.0.0.0/24", "10.0.1.0/24"]).intersection(set(["10.0.0.128/25", "10.0.1.0/25"]))));
+    XCTAssertEqual([], nets(set(["10.0.0.0/8"]).intersection(set(["11.0.0.0/8", "::/0"]))));
+    XCTAssertEqual(["2001:db8::/32"], nets(set(["10.0.0.0/8", "::/0"]).intersection(set(["2001:db8::/32"]))));
+  }
+  
+  func test_method_subtracting() {
+    XCTAssertEqual(["10.0.0.0/16", "10.1.0.0/17", "10.1.128.0/18", "10.1.192.0/19", "10.1.224.0/20",
+                    "10.1.240.0/21", "10.1.248.0/22", "10.1.252.0/23", "10.1.254.0/24", "10.2.0.0/15"],
+                   nets(set(["10.0.0.0/14"]).subtracting(set(["10.1.255.0/24"]))));
+    XCTAssertEqual(["10.0.1.0/24", "10.0.3.0/24"],
+                   nets(set(["10.0.0.0/22"]).subtracting(set(["10.0.0.0/24", "10.0.2.0/24", "11.0.0.0/8"]))));
+    XCTAssertEqual([], nets(set(["10.0.0.0/24"]).subtracting(set(["10.0.0.0/8"]))));
+    XCTAssertEqual(["10.0.0.0/24"], nets(set(["10.0.0.0/24"]).subtracting(set(["::/0"]))));
+    XCTAssertEqual(["10.0.0.0/24", "10.0.2.0/24"],
+                   nets(set(["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/24"]).subtracting(set(["10.0.1.0/24"]))));
+  }
+  
+  func test_method_symmetric_difference() {
+    XCTAssertEqual(["10.0.0.0/24", "10.0.2.0/24"],
+                   nets(set(["10.0.0.0/23"]).symmetric_difference(set(["10.0.1.0/24", "10.0.2.0/24"]))));
+  }
+  
+  func test_method_complement() {
+    let rfc1918 = set(["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"]);
+    let allowed = rfc1918.complement(try! IPAddress.parse("0.0.0.0/0"));
+    XCTAssertEqual(["0.0.0.0/5", "8.0.0.0/7", "11.0.0.0/8", "12.0.0.0/6", "16.0.0.0/4", "32.0.0.0/3",
+                    "64.0.0.0/2", "128.0.0.0/3", "160.0.0.0/5", "168.0.0.0/6", "172.0.0.0/12",
+                    "172.32.0.0/11", "172.64.0.0/10", "172.128.0.0/9", "173.0.0.0/8", "174.0.0.0/7",
+                    "176.0.0.0/4", "192.0.0.0/9", "192.128.0.0/11", "192.160.0.0/13", "192.169.0.0/16",
+                    "192.170.0.0/15", "192.172.0.0/14", "192.176.0.0/12", "192.192.0.0/10",
+                    "193.0.0.0/8", "194.0.0.0/7", "196.0.0.0/6", "200.0.0.0/5", "208.0.0.0/4",
+                    "224.0.0.0/3"], nets(allowed));
+    XCTAssertEqual((BigUInt(1) << 32) - rfc1918.size(), allowed.size());
+    XCTAssertEqual(["0.0.0.0/0"], nets(allowed.union(rfc1918)));
+    XCTAssertTrue(allowed.intersection(rfc1918).is_empty);
+    XCTAssertEqual(["::/0"], nets(rfc1918.complement(try! IPAddress.parse("::/0"))));
+    XCTAssertEqual(["::/1"], nets(set(["8000::/1"]).complement(try! IPAddress.parse("::/0"))));
+  }
+  
+  func test_method_size() {
+    XCTAssertEqual(BigUInt(0), IPAddressSet().size());
+    XCTAssertEqual(BigUInt(512), set(["10.0.0.0/24", "10.0.1.0/24", "10.0.0.0/25"]).size());
+    XCTAssertEqual((BigUInt(1) << 128) + (BigUInt(1) << 32), set(["::/0", "0.0.0.0/0"]).size());
+  }
+  
+  func test_method_includes() {
+    let set = self.set(["10.0.0.0/24", "10.0.2.0/23", "192.168.0.0/16", "2001:db8::/32"]);
+    XCTAssertTrue(set.includes(try! IPAddress.parse("10.0.0.1")));
+    XCTAssertTrue(set.includes(try! IPAddress.parse("10.0.0.0/24")));
+    XCTAssertTrue(set.includes(try! IPAddress.parse("10.0.3.255")));
+    XCTAssertTrue(set.includes(try! IPAddress.parse("192.168.255.255")));
+    XCTAssertTrue(set.includes(try! IPAddress.parse("2001:db8::1")));
+    XCTAssertFalse(set.includes(try! IPAddress.parse("10.0.1.1")));
+    XCTAssertFalse(set.includes(try! IPAddress.parse("10.0.0.0/23")));
+    XCTAssertFalse(set.includes(try! IPAddress.parse("9.255.255.255")));
+    XCTAssertFalse(set.includes(try! IPAddress.parse("::1")));
+    XCTAssertFalse(set.includes(try! IPAddress.parse("2001:db9::1")));
+    XCTAssertFalse(IPAddressSet().includes(try! IPAddress.parse("10.0.0.1")));
+  }
+  
+  static var allTests : [(String, (IPAddressSetTests) -> () throws -> Void)] {
+    return [
+      ("test_initialize", test_initialize),
+      ("test_method_union", test_method_union),
+      ("test_method_intersection", test_method_intersection),
+      ("test_method_subtracting", test_method_subtracting),
+      ("test_method_symmetric_difference", test_method_symmetric_difference),
+      ("test_method_complement", test_method_complement),
+      ("test_method_size", test_method_size),
+      ("test_method_includes", test_method_includes),
+    ]
+  }
+}
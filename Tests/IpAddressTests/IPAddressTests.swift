@@ -28,17 +28,17 @@ class IPAddressTests: XCTestCase {
   }
   
   func test_method_ipaddress() {
-    XCTAssertNotNil(IPAddress.parse(setup().valid_ipv4));
-    XCTAssertNotNil(IPAddress.parse(setup().valid_ipv6));
-    XCTAssertNotNil(IPAddress.parse(setup().valid_mapped));
+    XCTAssertNoThrow(try IPAddress.parse(setup().valid_ipv4));
+    XCTAssertNoThrow(try IPAddress.parse(setup().valid_ipv6));
+    XCTAssertNoThrow(try IPAddress.parse(setup().valid_mapped));
     
-    XCTAssertTrue(IPAddress.parse(setup().valid_ipv4)!.is_ipv4());
-    XCTAssertTrue(IPAddress.parse(setup().valid_ipv6)!.is_ipv6());
-    XCTAssertTrue(IPAddress.parse(setup().valid_mapped)!.is_mapped());
+    XCTAssertTrue(try! IPAddress.parse(setup().valid_ipv4).is_ipv4());
+    XCTAssertTrue(try! IPAddress.parse(setup().valid_ipv6).is_ipv6());
+    XCTAssertTrue(try! IPAddress.parse(setup().valid_mapped).is_mapped());
     
-    XCTAssertNil(IPAddress.parse(setup().invalid_ipv4));
-    XCTAssertNil(IPAddress.parse(setup().invalid_ipv6));
-    XCTAssertNil(IPAddress.parse(setup().invalid_mapped));
+    XCTAssertThrowsError(try IPAddress.parse(setup().invalid_ipv4));
+    XCTAssertThrowsError(try IPAddress.parse(setup().invalid_ipv6));
+    XCTAssertThrowsError(try IPAddress.parse(setup().invalid_mapped));
   }
   
   func test_module_method_valid() {
@@ -84,7 +84,7 @@ class IPAddressTests: XCTestCase {
     }
     var ip_addresses = [IPAddress]();
     for net in netstr {
-      ip_addresses.append(IPAddress.parse(net)!);
+      ip_addresses.append(try! IPAddress.parse(net));
     }
     
     let empty_vec = [String]();
@@ -196,20 +196,168 @@ class IPAddressTests: XCTestCase {
     // printer = RubyProf.GraphPrinter.new(result)
     // printer.print(STDOUT, {})
     // test imutable input parameters
-    let a1 = IPAddress.parse("10.0.0.1/24")!;
-    let a2 = IPAddress.parse("10.0.1.1/24")!;
+    let a1 = try! IPAddress.parse("10.0.0.1/24");
+    let a2 = try! IPAddress.parse("10.0.1.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([a1.clone(), a2.clone()])),
                    ["10.0.0.0/23"]);
     XCTAssertEqual("10.0.0.1/24", a1.to_string());
     XCTAssertEqual("10.0.1.1/24", a2.to_string());
   }
   
+  func test_value_semantics() {
+    let net = try! IPAddress.parse("10.0.0.1/24");
+    let copy = net;
+    XCTAssertEqual(IPAddress.to_string_vec(net.subnet(25)), ["10.0.0.0/25", "10.0.0.128/25"]);
+    XCTAssertEqual(net.change_prefix(16)!.to_string(), "10.0.0.1/16");
+    XCTAssertEqual(IPAddress.to_string_vec(IPAddress.aggregate([net, try! IPAddress.parse("10.0.1.1/24")])),
+                   ["10.0.0.0/23"]);
+    XCTAssertEqual("10.0.0.1/24", net.to_string());
+    XCTAssertEqual(copy, net);
+    let classful = try! IPAddress.parse("10.1.1.1");
+    XCTAssertEqual(8, try! Ipv4.parse_classful("10.1.1.1").prefix.num);
+    XCTAssertEqual(32, classful.prefix.num);
+    let mapped = try! IPAddress.parse("::ffff:ac10:a01/120");
+    XCTAssertEqual("172.16.10.1/24", mapped.mapped!.to_string());
+    XCTAssertEqual("172.16.10.0/24", mapped.network().mapped!.to_string());
+  }
+  
+  func test_method_format() {
+    for str in ["10.0.0.1/24", "2001:db8::8:800:200c:417a/64", "::ffff:c000:201/120", "fe80::1%eth0/64"] {
+      let ip = try! IPAddress.parse(str);
+      XCTAssertEqual(ip.to_string(), ip.format());
+      XCTAssertEqual(ip.to_s(), ip.format(FormatOptions(prefix: PrefixFormat.none)));
+      XCTAssertEqual(ip.to_string_uncompressed(), ip.format(FormatOptions(groups: GroupFormat.expanded)));
+    }
+    let mapped = try! IPAddress.parse("::ffff:c000:201/120");
+    XCTAssertEqual("0000:0000:0000:0000:0000:FFFF:C000:0201/120",
+                   mapped.format(FormatOptions(groups: GroupFormat.expanded, uppercase: true)));
+    XCTAssertEqual("::ffff:192.0.2.1", mapped.format(FormatOptions(embedded_ipv4: true, prefix: PrefixFormat.none)));
+    XCTAssertEqual("0000:0000:0000:0000:0000:ffff:192.0.2.1/120",
+                   mapped.format(FormatOptions(groups: GroupFormat.expanded, embedded_ipv4: true)));
+    XCTAssertEqual("64:ff9b::192.0.2.1/96", try! IPAddress.parse("64:ff9b::c000:201/96")
+                     .format(FormatOptions(embedded_ipv4: true)));
+    XCTAssertEqual("2001:db8::0.0.0.1", try! IPAddress.parse("2001:db8::1")
+                     .format(FormatOptions(embedded_ipv4: true, prefix: PrefixFormat.none)));
+    XCTAssertEqual("2001:db8:0:1:1:1:1:1", try! IPAddress.parse("2001:db8:0:1:1:1:1:1")
+                     .format(FormatOptions(groups: GroupFormat.canonical, prefix: PrefixFormat.none)));
+    XCTAssertEqual("172.16.10.1/255.255.255.0", try! IPAddress.parse("172.16.10.1/24")
+                     .format(FormatOptions(prefix: PrefixFormat.netmask)));
+    XCTAssertEqual("172.16.10.1/24", try! IPAddress.parse("172.16.10.1/24")
+                     .format(FormatOptions(groups: GroupFormat.expanded, uppercase: true, embedded_ipv4: true)));
+    XCTAssertEqual("2001:db8::1/ffff:ffff:ffff:ffff::", try! IPAddress.parse("2001:db8::1/64")
+                     .format(FormatOptions(prefix: PrefixFormat.netmask)));
+    XCTAssertEqual("FE80::1%eth0/64", try! IPAddress.parse("fe80::1%eth0/64")
+                     .format(FormatOptions(uppercase: true)));
+  }
+  
+  func test_conformances() {
+    let ip = IPAddress("10.0.0.1/24")!;
+    XCTAssertEqual("10.0.0.1/24", ip.description);
+    XCTAssertEqual("10.0.0.1/24", "\(ip)");
+    XCTAssertEqual("<IPAddress:10.0.0.1/24>", ip.debugDescription);
+    XCTAssertNil(IPAddress("10.0.0.256"));
+    for str in ["10.0.0.1/24", "2001:db8::1/64", "::ffff:c000:201/120", "fe80::1%eth0/64"] {
+      let ip = IPAddress(str)!;
+      XCTAssertEqual(ip, IPAddress(ip.description));
+    }
+    let set = Set([ip, try! IPAddress.parse("10.0.0.1/24"), try! IPAddress.parse("10.0.0.1/25"),
+                   try! IPAddress.parse("fe80::1%eth0"), try! IPAddress.parse("fe80::1%eth1")]);
+    XCTAssertEqual(4, set.count);
+    var dict = [IPAddress: String]();
+    dict[ip] = "gw";
+    XCTAssertEqual("gw", dict[try! IPAddress.parse("10.0.0.1/24")]);
+    XCTAssertNil(dict[try! IPAddress.parse("10.0.0.1/8")]);
+    let sorted = [IPAddress("::1")!, IPAddress("10.0.0.2")!, IPAddress("10.0.0.1/24")!, IPAddress("10.0.0.1/8")!].sorted();
+    XCTAssertEqual(["10.0.0.1/8", "10.0.0.1/24", "10.0.0.2/32", "::1/128"], IPAddress.to_string_vec(sorted));
+    XCTAssertTrue(IPAddress("10.0.0.1")! < IPAddress("::")!);
+    XCTAssertTrue(IPAddress("fe80::1")! < IPAddress("fe80::1%eth0")!);
+    XCTAssertEqual(ip.lt(IPAddress("10.0.0.2/24")!), ip < IPAddress("10.0.0.2/24")!);
+  }
+  
+  func test_method_hosts() {
+    let net = try! IPAddress.parse("10.0.0.1/29");
+    XCTAssertEqual(["10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5", "10.0.0.6"],
+                   net.hosts.map({ $0.to_s() }));
+    XCTAssertEqual(6, net.hosts.count);
+    XCTAssertEqual(8, net.addresses.count);
+    XCTAssertEqual("10.0.0.6/29", net.hosts[5].to_string());
+    XCTAssertEqual("10.0.0.6", net.hosts[-1].to_s());
+    XCTAssertEqual("10.0.0.1", net.hosts[-6].to_s());
+    XCTAssertEqual("10.0.0.7", net.addresses[-1].to_s());
+    XCTAssertEqual(["10.0.0.7", "10.0.0.6"], net.addresses.reversed().prefix(2).map({ $0.to_s() }));
+    XCTAssertEqual(["10.0.0.0", "10.0.0.1"], net.addresses.prefix(2).map({ $0.to_s() }));
+    XCTAssertTrue(try! IPAddress.parse("10.0.0.1/31").hosts.isEmpty);
+    XCTAssertTrue(try! IPAddress.parse("0.0.0.0/32").hosts.isEmpty);
+    XCTAssertEqual(["10.0.0.1", "10.0.0.2"], try! IPAddress.parse("10.0.0.1/30").hosts.map({ $0.to_s() }));
+    XCTAssertEqual(["255.255.255.255"], try! IPAddress.parse("255.255.255.255/32").addresses.map({ $0.to_s() }));
+    let v6 = try! IPAddress.parse("2001:db8::/64");
+    XCTAssertEqual("2001:db8::5", v6.hosts[5].to_s());
+    XCTAssertEqual("2001:db8::ffff:ffff:ffff:ffff", v6.hosts[-1].to_s());
+    XCTAssertEqual(["2001:db8::", "2001:db8::1", "2001:db8::2"], v6.hosts.prefix(3).map({ $0.to_s() }));
+    XCTAssertEqual(["2001:db8::ffff:ffff:ffff:ffff", "2001:db8::ffff:ffff:ffff:fffe"],
+                   v6.hosts.reversed().prefix(2).map({ $0.to_s() }));
+    let all = try! IPAddress.parse("::/0");
+    XCTAssertEqual("::", all.addresses[0].to_s());
+    XCTAssertEqual("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff", all.addresses[-1].to_s());
+    XCTAssertEqual(["::", "::1"], all.addresses.prefix(2).map({ $0.to_s() }));
+    XCTAssertEqual(1, try! IPAddress.parse("::1").hosts.count);
+    XCTAssertEqual("fe80::2%eth0/64", try! IPAddress.parse("fe80::1%eth0/64").hosts[2].to_string());
+    let hosts = net.hosts;
+    XCTAssertEqual(3, hosts.distance(from: hosts.index(hosts.startIndex, offsetBy: 2), to: hosts.index(before: hosts.endIndex)));
+    XCTAssertEqual(-6, hosts.distance(from: hosts.endIndex, to: hosts.startIndex));
+    var found: IPAddress? = nil;
+    for ip in v6.hosts where ip.address.lo == 3 {
+      found = ip;
+      break;
+    }
+    XCTAssertEqual("2001:db8::3/64", found!.to_string());
+  }
+  
+  func test_classmethod_from_dns_reverse() {
+    XCTAssertEqual("1.2.3.4/32", try! IPAddress.from_dns_reverse("4.3.2.1.in-addr.arpa").to_string());
+    XCTAssertEqual("1.2.3.0/24", try! IPAddress.from_dns_reverse("3.2.1.in-addr.arpa").to_string());
+    XCTAssertEqual("10.0.0.0/8", try! IPAddress.from_dns_reverse("10.IN-ADDR.ARPA.").to_string());
+    XCTAssertEqual("0.0.0.0/0", try! IPAddress.from_dns_reverse("in-addr.arpa").to_string());
+    XCTAssertEqual("2001:db8::1/128", try! IPAddress.from_dns_reverse(
+      "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa").to_string());
+    XCTAssertEqual("2001:db8::/32", try! IPAddress.from_dns_reverse("8.B.D.0.1.0.0.2.ip6.arpa.").to_string());
+    XCTAssertEqual("2001:db0::/28", try! IPAddress.from_dns_reverse("b.d.0.1.0.0.2.ip6.arpa").to_string());
+    XCTAssertEqual("::/0", try! IPAddress.from_dns_reverse("ip6.arpa").to_string());
+    for str in ["172.16.10.1/24", "10.0.0.0/8", "192.0.2.1", "2001:db8::/64", "2001:db8:1:2::3", "fe80::/12"] {
+      let ip = try! IPAddress.parse(str);
+      XCTAssertEqual(ip.network(), try! IPAddress.from_dns_reverse(ip.dns_reverse()), str);
+    }
+    let errors: [(String, IPAddressParseError)] = [
+      ("4.3.256.1.in-addr.arpa", IPAddressParseError.invalid_reverse_label("256", 4)),
+      ("4.3.02.1.in-addr.arpa", IPAddressParseError.invalid_reverse_label("02", 4)),
+      ("4.3.+2.1.in-addr.arpa", IPAddressParseError.invalid_reverse_label("+2", 4)),
+      ("4..1.in-addr.arpa", IPAddressParseError.invalid_reverse_label("", 2)),
+      ("5.4.3.2.1.in-addr.arpa", IPAddressParseError.invalid_reverse_label("5", 0)),
+      ("10.b.d.0.1.0.0.2.ip6.arpa", IPAddressParseError.invalid_reverse_label("10", 0)),
+      ("8.g.d.0.ip6.arpa", IPAddressParseError.invalid_reverse_label("g", 2)),
+      ("4.3.2.1.example.com", IPAddressParseError.unknown_reverse_domain("4.3.2.1.example.com", 0)),
+      ("arpa", IPAddressParseError.unknown_reverse_domain("arpa", 0)),
+      ("", IPAddressParseError.unknown_reverse_domain("", 0)),
+    ];
+    for (name, error) in errors {
+      XCTAssertThrowsError(try IPAddress.from_dns_reverse(name), name) { err in
+        XCTAssertEqual(error, err as? IPAddressParseError, name);
+      }
+    }
+    XCTAssertThrowsError(try IPAddress.from_dns_reverse(String(repeating: "0.", count: 33) + "ip6.arpa"));
+  }
+  
   static var allTests : [(String, (IPAddressTests) -> () throws -> Void)] {
     return [
       ("test_method_ipaddress", test_method_ipaddress),
       ("test_module_method_valid", test_module_method_valid),
       ("test_module_method_valid_ipv4_netmark", test_module_method_valid_ipv4_netmark),
-      ("test_summarize", test_summarize)
+      ("test_summarize", test_summarize),
+      ("test_value_semantics", test_value_semantics),
+      ("test_method_format", test_method_format),
+      ("test_conformances", test_conformances),
+      ("test_method_hosts", test_method_hosts),
+      ("test_classmethod_from_dns_reverse", test_classmethod_from_dns_reverse)
     ]
   }
   
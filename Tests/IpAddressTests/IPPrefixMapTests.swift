@@ -0,0 +1,143 @@
+import XCTest
+@testable import IpAddress
+
+class IPPrefixMapTests : XCTestCase {
+  
+  func keys(_ entries: [(key: IPAddress, value: Int)]) -> [String] {
+    return entries.map({ $0.key.to_string() });
+  }
+  
+  func setup() -> IPPrefixMap<Int> {
+    var map = IPPrefixMap<Int>();
+    map.insert(ip("10.0.0.0/8"), 1);
+    map.insert(ip("10.1.0.0/16"), 2);
+    map.insert(ip("10.1.2.0/24"), 3);
+    map.insert(ip("10.2.0.0/16"), 4);
+    map.insert(ip("192.168.0.0/16"), 5);
+    map.insert(ip("0.0.0.0/0"), 6);
+    map.insert(ip("2001:db8::/32"), 7);
+    map.insert(ip("2001:db8:1::/48"), 8);
+    map.insert(ip("::/0"), 9);
+    return map;
+  }
+  
+  func test_insert_get() {
+    var map = setup();
+    XCTAssertEqual(9, map.count);
+    XCTAssertEqual(1, map.get(ip("10.0.0.0/8")));
+    XCTAssertEqual(1, map.get(ip("10.9.9.9/8")));
+    XCTAssertEqual(3, map[ip("10.1.2.0/24")]);
+    XCTAssertEqual(8, map[ip("2001:db8:1::/48")]);
+    XCTAssertNil(map.get(ip("10.0.0.0/9")));
+    XCTAssertNil(map.get(ip("10.1.0.0/17")));
+    XCTAssertNil(map.get(ip("11.0.0.0/8")));
+    XCTAssertNil(map.get(ip("2001:db8::/33")));
+    XCTAssertEqual(1, map.insert(ip("10.0.0.0/8"), 10));
+    XCTAssertEqual(10, map[ip("10.0.0.0/8")]);
+    XCTAssertEqual(9, map.count);
+    map[ip("10.0.0.0/9")] = 11;
+    XCTAssertEqual(10, map.count);
+    XCTAssertEqual(11, map[ip("10.0.0.0/9")]);
+    XCTAssertEqual(10, map[ip("10.0.0.0/8")]);
+  }
+  
+  func test_remove() {
+    var map = setup();
+    XCTAssertEqual(2, map.remove(ip("10.1.0.0/16")));
+    XCTAssertNil(map.remove(ip("10.1.0.0/16")));
+    XCTAssertNil(map.remove(ip("10.3.0.0/16")));
+    XCTAssertEqual(8, map.count);
+    XCTAssertNil(map[ip("10.1.0.0/16")]);
+    XCTAssertEqual(3, map[ip("10.1.2.0/24")]);
+    XCTAssertEqual(4, map[ip("10.2.0.0/16")]);
+    map[ip("0.0.0.0/0")] = nil;
+    map[ip("10.0.0.0/8")] = nil;
+    XCTAssertEqual(["10.1.2.0/24", "10.2.0.0/16", "192.168.0.0/16", "::/0", "2001:db8::/32", "2001:db8:1::/48"],
+                   keys(Array(map)));
+    for (key, _) in map {
+      map.remove(key);
+    }
+    XCTAssertTrue(map.is_empty);
+    XCTAssertEqual(0, Array(map).count);
+  }
+  
+  func test_longest_match() {
+    let map = setup();
+    XCTAssertEqual(3, map.longest_match(ip("10.1.2.3"))!.value);
+    XCTAssertEqual("10.1.0.0/16", map.longest_match(ip("10.1.3.3"))!.key.to_string());
+    XCTAssertEqual(1, map.longest_match(ip("10.3.0.1"))!.value);
+    XCTAssertEqual(6, map.longest_match(ip("11.0.0.1"))!.value);
+    XCTAssertEqual(2, map.longest_match(ip("10.1.0.0/20"))!.value);
+    XCTAssertEqual(8, map.longest_match(ip("2001:db8:1:2::1"))!.value);
+    XCTAssertEqual(9, map.longest_match(ip("fe80::1%eth0"))!.value);
+    var v4 = IPPrefixMap<Int>();
+    v4.insert(ip("10.0.0.0/8"), 1);
+    XCTAssertNil(v4.longest_match(ip("11.0.0.1")));
+    XCTAssertNil(v4.longest_match(ip("::ffff:a00:1")));
+  }
+  
+  func test_covering() {
+    let map = setup();
+    XCTAssertEqual(["0.0.0.0/0", "10.0.0.0/8", "10.1.0.0/16", "10.1.2.0/24"], keys(map.covering(ip("10.1.2.3"))));
+    XCTAssertEqual(["0.0.0.0/0", "10.0.0.0/8", "10.1.0.0/16"], keys(map.covering(ip("10.1.0.0/16"))));
+    XCTAssertEqual(["::/0", "2001:db8::/32"], keys(map.covering(ip("2001:db8:2::1"))));
+  }
+  
+  func test_covered() {
+    let map = setup();
+    XCTAssertEqual(["10.0.0.0/8", "10.1.0.0/16", "10.1.2.0/24", "10.2.0.0/16"], keys(map.covered(ip("10.0.0.0/8"))));
+    XCTAssertEqual(["10.1.0.0/16", "10.1.2.0/24", "10.2.0.0/16"], keys(map.covered(ip("10.0.0.0/14"))));
+    XCTAssertEqual(["10.1.2.0/24"], keys(map.covered(ip("10.1.2.0/23"))));
+    XCTAssertEqual([], keys(map.covered(ip("10.1.3.0/24"))));
+    XCTAssertEqual([], keys(map.covered(ip("172.16.0.0/12"))));
+    XCTAssertEqual(6, map.covered(ip("0.0.0.0/0")).count);
+    XCTAssertEqual(["2001:db8::/32", "2001:db8:1::/48"], keys(map.covered(ip("2001::/16"))));
+  }
+  
+  func test_ordered_iteration() {
+    let map = setup();
+    let all = keys(Array(map));
+    XCTAssertEqual(["0.0.0.0/0", "10.0.0.0/8", "10.1.0.0/16", "10.1.2.0/24", "10.2.0.0/16", "192.168.0.0/16",
+                    "::/0", "2001:db8::/32", "2001:db8:1::/48"], all);
+    XCTAssertEqual(IPAddress.to_string_vec(Array(map).map({ $0.key }).sorted()), all);
+  }
+  
+  func test_value_semantics() {
+    var map = setup();
+    let copy = map;
+    map[ip("10.1.2.0/24")] = 30;
+    map.remove(ip("10.0.0.0/8"));
+    XCTAssertEqual(3, copy[ip("10.1.2.0/24")]);
+    XCTAssertEqual(1, copy[ip("10.0.0.0/8")]);
+    XCTAssertEqual(9, copy.count);
+    XCTAssertEqual(30, map[ip("10.1.2.0/24")]);
+    XCTAssertEqual(8, map.count);
+  }
+  
+  func test_many_prefixes() {
+    var map = IPPrefixMap<Int>();
+    for i in 0..<256 {
+      map.insert(ip("10.\(i).0.0/16"), i);
+      map.insert(ip("10.\(i).\(i).0/24"), 1000 + i);
+    }
+    XCTAssertEqual(512, map.count);
+    for i in 0..<256 {
+      XCTAssertEqual(i, map.longest_match(ip("10.\(i).\((i + 1) % 256).1"))!.value);
+      XCTAssertEqual(1000 + i, map.longest_match(ip("10.\(i).\(i).1"))!.value);
+    }
+    XCTAssertEqual(512, map.covered(ip("10.0.0.0/8")).count);
+  }
+  
+  static var allTests : [(String, (IPPrefixMapTests) -> () throws -> Void)] {
+    return [
+      ("test_insert_get", test_insert_get),
+      ("test_remove", test_remove),
+      ("test_longest_match", test_longest_match),
+      ("test_covering", test_covering),
+      ("test_covered", test_covered),
+      ("test_ordered_iteration", test_ordered_iteration),
+      ("test_value_semantics", test_value_semantics),
+      ("test_many_prefixes", test_many_prefixes),
+    ]
+  }
+}
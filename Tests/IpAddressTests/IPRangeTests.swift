@@ -0,0 +1,101 @@
+import XCTest
+import BigInt
+@testable import IpAddress
+
+class IPRangeTests : XCTestCase {
+  
+  func test_parse() {
+    let range = try! IPRange.parse("10.0.0.5 - 10.0.1.20");
+    XCTAssertEqual("10.0.0.5/32", range.first.to_string());
+    XCTAssertEqual("10.0.1.20/32", range.last.to_string());
+    XCTAssertEqual("10.0.0.5-10.0.1.20", range.description);
+    XCTAssertEqual(range, try! IPRange.parse("10.0.0.5-10.0.1.20"));
+    XCTAssertEqual("2001:db8::-2001:db8::ffff", try! IPRange.parse("2001:db8::/112").description);
+    XCTAssertEqual("10.0.0.1-10.0.0.1", try! IPRange.parse("10.0.0.1").description);
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.5 - 10.0.0.1")) { err in
+      XCTAssertEqual(IPAddressParseError.reversed_range("10.0.0.5 - 10.0.0.1", 0), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.5-10.0.1.256")) { err in
+      XCTAssertEqual(IPAddressParseError.octet_out_of_range("256", 16), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.5-::1")) { err in
+      XCTAssertEqual(IPAddressParseError.invalid_range("::1", 9), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.0/24-10.0.1.0"));
+    XCTAssertThrowsError(try IPRange.parse("fe80::1%eth0-fe80::2%eth0"));
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.1-10.0.0.2-10.0.0.3"));
+    XCTAssertThrowsError(try IPRange.parse("10.0.0.1-"));
+    XCTAssertNil(IPRange(ip("10.0.0.2"), ip("10.0.0.1")));
+    XCTAssertNil(IPRange(ip("10.0.0.1"), ip("::2")));
+  }
+  
+  func test_method_size() {
+    XCTAssertEqual(BigUInt(272), try! IPRange.parse("10.0.0.5-10.0.1.20").size());
+    XCTAssertEqual(BigUInt(1), try! IPRange.parse("10.0.0.5-10.0.0.5").size());
+    XCTAssertEqual(BigUInt(1) << 32, IPRange(ip("0.0.0.0/0")).size());
+    XCTAssertEqual(BigUInt(1) << 128, IPRange(ip("::/0")).size());
+  }
+  
+  func test_method_includes() {
+    let range = try! IPRange.parse("10.0.0.5-10.0.1.20");
+    XCTAssertTrue(range.includes(ip("10.0.0.5")));
+    XCTAssertTrue(range.includes(ip("10.0.1.20")));
+    XCTAssertTrue(range.includes(ip("10.0.0.128/25")));
+    XCTAssertFalse(range.includes(ip("10.0.0.4")));
+    XCTAssertFalse(range.includes(ip("10.0.1.21")));
+    XCTAssertFalse(range.includes(ip("10.0.0.0/24")));
+    XCTAssertFalse(range.includes(ip("::ffff:a00:6")));
+    XCTAssertTrue(range.includes(try! IPRange.parse("10.0.0.10-10.0.0.20")));
+    XCTAssertFalse(range.includes(try! IPRange.parse("10.0.0.1-10.0.0.20")));
+  }
+  
+  func test_method_overlaps() {
+    let range = try! IPRange.parse("10.0.0.5-10.0.1.20");
+    XCTAssertTrue(range.overlaps(try! IPRange.parse("10.0.1.20-10.0.2.0")));
+    XCTAssertTrue(range.overlaps(try! IPRange.parse("10.0.0.0-10.0.0.5")));
+    XCTAssertTrue(range.overlaps(try! IPRange.parse("0.0.0.0/0")));
+    XCTAssertFalse(range.overlaps(try! IPRange.parse("10.0.1.21-10.0.2.0")));
+    XCTAssertFalse(range.overlaps(try! IPRange.parse("10.0.0.0-10.0.0.4")));
+    XCTAssertFalse(range.overlaps(try! IPRange.parse("::/0")));
+  }
+  
+  func test_method_networks() {
+    XCTAssertEqual(["10.0.0.5/32", "10.0.0.6/31", "10.0.0.8/29", "10.0.0.16/28", "10.0.0.32/27",
+                    "10.0.0.64/26", "10.0.0.128/25", "10.0.1.0/28", "10.0.1.16/30", "10.0.1.20/32"],
+                   IPAddress.to_string_vec(try! IPRange.parse("10.0.0.5-10.0.1.20").networks()));
+    XCTAssertEqual(["10.0.0.0/24"], IPAddress.to_string_vec(try! IPRange.parse("10.0.0.0-10.0.0.255").networks()));
+    XCTAssertEqual(["0.0.0.0/0"], IPAddress.to_string_vec(IPRange(ip("0.0.0.0/0")).networks()));
+    XCTAssertEqual(["::/0"], IPAddress.to_string_vec(IPRange(ip("::/0")).networks()));
+    XCTAssertEqual(["255.255.255.255/32"],
+                   IPAddress.to_string_vec(try! IPRange.parse("255.255.255.255-255.255.255.255").networks()));
+    XCTAssertEqual(33, try! IPRange.parse("::1-::1:ffff:ffff").networks().count);
+    let all = try! IPRange.parse("::1-ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe").networks();
+    XCTAssertEqual(254, all.count);
+    XCTAssertEqual("::1/128", all[0].to_string());
+    XCTAssertEqual("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/128", all[253].to_string());
+    for range in ["10.0.0.5-10.0.1.20", "10.0.0.0-10.255.255.254", "2001:db8::5-2001:db8::1:7"] {
+      XCTAssertEqual([try! IPRange.parse(range)], IPRange.from_networks(try! IPRange.parse(range).networks()));
+    }
+  }
+  
+  func test_classmethod_from_networks() {
+    XCTAssertEqual(["10.0.0.0-10.0.1.127", "10.0.3.0-10.0.3.255", "2001:db8::-2001:db8:1:ffff:ffff:ffff:ffff:ffff"],
+                   IPRange.from_networks(IPAddress.to_ipaddress_vec(
+                    ["2001:db8:1::/48", "10.0.3.0/24", "10.0.1.0/25", "10.0.0.0/24", "10.0.0.128/25",
+                     "2001:db8::/48"])!).map({ $0.description }));
+    XCTAssertEqual(["0.0.0.0-255.255.255.255"],
+                   IPRange.from_networks([ip("0.0.0.0/1"), ip("128.0.0.0/1"), ip("10.0.0.0/8")]).map({ $0.description }));
+    XCTAssertEqual([], IPRange.from_networks([]));
+  }
+  
+  static var allTests : [(String, (IPRangeTests) -> () throws -> Void)] {
+    return [
+      ("test_parse", test_parse),
+      ("test_method_size", test_method_size),
+      ("test_method_includes", test_method_includes),
+      ("test_method_overlaps", test_method_overlaps),
+      ("test_method_networks", test_method_networks),
+      ("test_classmethod_from_networks", test_classmethod_from_networks),
+    ]
+  }
+}
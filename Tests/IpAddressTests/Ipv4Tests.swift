@@ -20,13 +20,13 @@ class IPv4Test {
   let valid_ipv4_range = ["10.0.0.1-254", "10.0.1-254.0", "10.1-254.0.0"];
   var netmask_values = [String: String]();
   var decimal_values = [String: BigUInt]();
-  let ip: IPAddress = IPAddress.parse("172.16.10.1/24")!;
-  let network: IPAddress = IPAddress.parse("172.16.10.0/24")!;
+  let ip: IPAddress = try! IPAddress.parse("172.16.10.1/24");
+  let network: IPAddress = try! IPAddress.parse("172.16.10.0/24");
   var networks = [String: String]();
   var broadcast = [String: String]();
-  let class_a: IPAddress = IPAddress.parse("10.0.0.1/8")!;
-  let class_b: IPAddress = IPAddress.parse("172.16.0.1/16")!;
-  let class_c: IPAddress = IPAddress.parse("192.168.0.1/24")!;
+  let class_a: IPAddress = try! IPAddress.parse("10.0.0.1/8");
+  let class_b: IPAddress = try! IPAddress.parse("172.16.0.1/16");
+  let class_c: IPAddress = try! IPAddress.parse("192.168.0.1/24");
   var classful = [String: UInt8]()
 }
 
@@ -99,46 +99,76 @@ class Ipv4Tests : XCTestCase {
   func test_initialize() {
     for (addr, _) in setup().valid_ipv4 {
       //console.log(i[0]);
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertTrue(ip.is_ipv4() && ip.is_ipv6() == false);
     }
     XCTAssertEqual(32, setup().ip.prefix.ip_bits.bits);
-    XCTAssertNil(IPAddress.parse("1.f.13.1/-3"));
-    XCTAssertNotNil(IPAddress.parse("10.0.0.0/8"));
+    XCTAssertThrowsError(try IPAddress.parse("1.f.13.1/-3"));
+    XCTAssertNoThrow(try IPAddress.parse("10.0.0.0/8"));
   }
   func test_initialize_format_error() {
     for i in setup().invalid_ipv4 {
-      XCTAssertNil(IPAddress.parse(i));
+      XCTAssertThrowsError(try IPAddress.parse(i));
     }
-    XCTAssertNil(IPAddress.parse("10.0.0.0/asd"));
+    XCTAssertThrowsError(try IPAddress.parse("10.0.0.0/asd"));
+  }
+  func test_initialize_strict() {
+    let strict = ParseOptions.strict;
+    XCTAssertTrue(IPAddress.is_valid_ipv4("10.1"));
+    XCTAssertTrue(IPAddress.is_valid_ipv4("010.0.0.1"));
+    XCTAssertFalse(IPAddress.is_valid_ipv4("10.1", strict));
+    XCTAssertFalse(IPAddress.is_valid_ipv4("127.1", strict));
+    XCTAssertFalse(IPAddress.is_valid_ipv4("010.0.0.1", strict));
+    XCTAssertFalse(IPAddress.is_valid("10.0.0.01", strict));
+    XCTAssertFalse(IPAddress.is_valid_ipv4("+10.0.0.1", strict));
+    XCTAssertFalse(IPAddress.is_valid_ipv4("+010.0.0.1", strict));
+    XCTAssertTrue(IPAddress.is_valid("10.0.0.1", strict));
+    XCTAssertTrue(IPAddress.is_valid("0.0.0.0", strict));
+    XCTAssertTrue(IPAddress.is_valid("2001:db8::1", strict));
+    XCTAssertEqual("127.0.0.1/32", try! IPAddress.parse("127.1").to_string());
+    XCTAssertEqual("10.0.0.1/32", try! IPAddress.parse("010.0.0.1").to_string());
+    XCTAssertEqual("10.0.0.1/24", try! IPAddress.parse("10.0.0.1/24", strict).to_string());
+    XCTAssertThrowsError(try IPAddress.parse("127.1", strict)) { err in
+      XCTAssertEqual(IPAddressParseError.too_few_octets("127.1", 0), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("10.0.00.1/8", strict)) { err in
+      XCTAssertEqual(IPAddressParseError.octet_leading_zero("00", 5), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("+010.0.0.1", strict)) { err in
+      XCTAssertEqual(IPAddressParseError.octet_not_decimal("+010", 0), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("10.0.0.1/255.255.0", strict));
+    XCTAssertNoThrow(try IPAddress.parse("10.0.0.1/255.255.0"));
+    XCTAssertThrowsError(try IPAddress.parse("::ffff:10.1", strict));
+    XCTAssertNoThrow(try IPAddress.parse("::ffff:10.0.0.1", strict));
   }
   func test_initialize_without_prefix() {
-    XCTAssertNotNil(IPAddress.parse("10.10.0.0"));
-    let ip = IPAddress.parse("10.10.0.0")!;
+    XCTAssertNoThrow(try IPAddress.parse("10.10.0.0"));
+    let ip = try! IPAddress.parse("10.10.0.0");
     XCTAssertTrue(ip.is_ipv6()==false && ip.is_ipv4());
     XCTAssertEqual(32, ip.prefix.num);
   }
   func test_attributes() {
     for (arg, attr) in setup().valid_ipv4 {
-      let ip = IPAddress.parse(arg)!;
+      let ip = try! IPAddress.parse(arg);
       // println!("test_attributes:{}:{:?}", arg, attr);
       XCTAssertEqual(attr.ip, ip.to_s());
       XCTAssertEqual(attr.prefix, ip.prefix.num);
     }
   }
   func test_octets() {
-    let ip = IPAddress.parse("10.1.2.3/8")!;
+    let ip = try! IPAddress.parse("10.1.2.3/8");
     XCTAssertEqual(ip.parts(), [10, 1, 2, 3]);
   }
   func test_method_to_string() {
     for (arg, attr) in setup().valid_ipv4 {
-      let ip = IPAddress.parse(arg)!;
+      let ip = try! IPAddress.parse(arg);
       XCTAssertEqual("\(attr.ip)/\(attr.prefix)", ip.to_string());
     }
   }
   func test_method_to_s() {
     for (arg, attr) in setup().valid_ipv4 {
-      let ip = IPAddress.parse(arg)!;
+      let ip = try! IPAddress.parse(arg);
       XCTAssertEqual(attr.ip, ip.to_s());
       // let ip_c = IPAddress.parse(arg);
       // XCTAssertEqual(attr.ip, ip.to_s());
@@ -146,13 +176,13 @@ class Ipv4Tests : XCTestCase {
   }
   func test_netmask() {
     for (addr, mask) in setup().netmask_values {
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertEqual(ip.netmask().to_s(), mask);
     }
   }
   func test_method_to_u32() {
     for (addr, int) in setup().decimal_values {
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertEqual(ip.host_address, int);
     }
   }
@@ -161,52 +191,52 @@ class Ipv4Tests : XCTestCase {
     XCTAssertEqual(false, setup().ip.is_network());
   }
   func test_one_address_network() {
-    let network = IPAddress.parse("172.16.10.1/32")!;
+    let network = try! IPAddress.parse("172.16.10.1/32");
     XCTAssertEqual(false, network.is_network());
   }
   func test_method_broadcast() {
     for (addr, bcast) in setup().broadcast {
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertEqual(bcast, ip.broadcast().to_string());
     }
   }
   
   func test_method_network() {
     for (addr, net) in setup().networks {
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertEqual(net, ip.network().to_string());
     }
   }
   
   
   func test_method_bits() {
-    let ip = IPAddress.parse("127.0.0.1")!;
+    let ip = try! IPAddress.parse("127.0.0.1");
     XCTAssertEqual("01111111000000000000000000000001", ip.bits());
   }
   
   func test_method_first() {
-    var ip = IPAddress.parse("192.168.100.0/24")!;
+    var ip = try! IPAddress.parse("192.168.100.0/24");
     XCTAssertEqual("192.168.100.1", ip.first().to_s());
-    ip = IPAddress.parse("192.168.100.50/24")!;
+    ip = try! IPAddress.parse("192.168.100.50/24");
     XCTAssertEqual("192.168.100.1", ip.first().to_s());
   }
   
   func test_method_last() {
-    var ip = IPAddress.parse("192.168.100.0/24")!;
+    var ip = try! IPAddress.parse("192.168.100.0/24");
     XCTAssertEqual("192.168.100.254", ip.last().to_s());
-    ip = IPAddress.parse("192.168.100.50/24")!;
+    ip = try! IPAddress.parse("192.168.100.50/24");
     XCTAssertEqual("192.168.100.254", ip.last().to_s());
   }
   
   func test_method_each_host() {
-    let ip = IPAddress.parse("10.0.0.1/29")!;
+    let ip = try! IPAddress.parse("10.0.0.1/29");
     var arr = [String]();
     ip.each_host({ arr.append($0.to_s()) });
     XCTAssertEqual(arr, ["10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5", "10.0.0.6"]);
   }
   
   func test_method_each() {
-    let ip = IPAddress.parse("10.0.0.1/29")!;
+    let ip = try! IPAddress.parse("10.0.0.1/29");
     var arr = [String]();
     ip.each({ arr.append($0.to_s()) });
     XCTAssertEqual(arr, ["10.0.0.0", "10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5",
@@ -214,7 +244,7 @@ class Ipv4Tests : XCTestCase {
   }
   
   func test_method_size() {
-    let ip = IPAddress.parse("10.0.0.1/29")!;
+    let ip = try! IPAddress.parse("10.0.0.1/29");
     XCTAssertEqual(ip.size(), BigUInt(8));
   }
   
@@ -227,30 +257,30 @@ class Ipv4Tests : XCTestCase {
   }
   
   func test_method_include() {
-    var ip = IPAddress.parse("192.168.10.100/24")!;
-    let addr = IPAddress.parse("192.168.10.102/24")!;
+    var ip = try! IPAddress.parse("192.168.10.100/24");
+    let addr = try! IPAddress.parse("192.168.10.102/24");
     XCTAssertEqual(true, ip.includes(addr));
-    XCTAssertEqual(false, ip.includes(IPAddress.parse("172.16.0.48")!));
-    ip = IPAddress.parse("10.0.0.0/8")!;
-    XCTAssertEqual(true, ip.includes(IPAddress.parse("10.0.0.0/9")!));
-    XCTAssertEqual(true, ip.includes(IPAddress.parse("10.1.1.1/32")!));
-    XCTAssertEqual(true, ip.includes(IPAddress.parse("10.1.1.1/9")!));
+    XCTAssertEqual(false, ip.includes(try! IPAddress.parse("172.16.0.48")));
+    ip = try! IPAddress.parse("10.0.0.0/8");
+    XCTAssertEqual(true, ip.includes(try! IPAddress.parse("10.0.0.0/9")));
+    XCTAssertEqual(true, ip.includes(try! IPAddress.parse("10.1.1.1/32")));
+    XCTAssertEqual(true, ip.includes(try! IPAddress.parse("10.1.1.1/9")));
     XCTAssertEqual(false,
-                   ip.includes(IPAddress.parse("172.16.0.0/16")!));
-    XCTAssertEqual(false, ip.includes(IPAddress.parse("10.0.0.0/7")!));
-    XCTAssertEqual(false, ip.includes(IPAddress.parse("5.5.5.5/32")!));
-    XCTAssertEqual(false, ip.includes(IPAddress.parse("11.0.0.0/8")!));
-    ip = IPAddress.parse("13.13.0.0/13")!;
+                   ip.includes(try! IPAddress.parse("172.16.0.0/16")));
+    XCTAssertEqual(false, ip.includes(try! IPAddress.parse("10.0.0.0/7")));
+    XCTAssertEqual(false, ip.includes(try! IPAddress.parse("5.5.5.5/32")));
+    XCTAssertEqual(false, ip.includes(try! IPAddress.parse("11.0.0.0/8")));
+    ip = try! IPAddress.parse("13.13.0.0/13");
     XCTAssertEqual(false,
-                   ip.includes(IPAddress.parse("13.16.0.0/32")!));
+                   ip.includes(try! IPAddress.parse("13.16.0.0/32")));
   }
   
   func test_method_include_all() {
-    let ip = IPAddress.parse("192.168.10.100/24")!;
-    let addr1 = IPAddress.parse("192.168.10.102/24")!;
-    let addr2 = IPAddress.parse("192.168.10.103/24")!;
+    let ip = try! IPAddress.parse("192.168.10.100/24");
+    let addr1 = try! IPAddress.parse("192.168.10.102/24");
+    let addr2 = try! IPAddress.parse("192.168.10.103/24");
     XCTAssertEqual(true, ip.includes_all([addr1, addr2]));
-    XCTAssertEqual(false, ip.includes_all([addr1, IPAddress.parse("13.16.0.0/32")!]));
+    XCTAssertEqual(false, ip.includes_all([addr1, try! IPAddress.parse("13.16.0.0/32")]));
   }
   
   func test_method_ipv4() {
@@ -262,19 +292,19 @@ class Ipv4Tests : XCTestCase {
   }
   
   func test_method_private() {
-    XCTAssertEqual(true, IPAddress.parse("169.254.99.4/24")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("192.168.10.50/24")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("192.168.10.50/16")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("172.16.77.40/24")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("172.16.10.50/14")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("10.10.10.10/10")!.is_private());
-    XCTAssertEqual(true, IPAddress.parse("10.0.0.0/8")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("192.168.10.50/12")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("3.3.3.3")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("10.0.0.0/7")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("172.32.0.0/12")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("172.16.0.0/11")!.is_private());
-    XCTAssertEqual(false, IPAddress.parse("192.0.0.2/24")!.is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("169.254.99.4/24").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("192.168.10.50/24").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("192.168.10.50/16").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("172.16.77.40/24").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("172.16.10.50/14").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("10.10.10.10/10").is_private());
+    XCTAssertEqual(true, try! IPAddress.parse("10.0.0.0/8").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("192.168.10.50/12").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("3.3.3.3").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("10.0.0.0/7").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("172.32.0.0/12").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("172.16.0.0/11").is_private());
+    XCTAssertEqual(false, try! IPAddress.parse("192.0.0.2/24").is_private());
   }
   
   func test_method_octet() {
@@ -306,18 +336,37 @@ class Ipv4Tests : XCTestCase {
     XCTAssertEqual("::ac10:a01", setup().ip.to_ipv6().to_s());
   }
   
+  func test_method_to_ipv6_embedding() {
+    let ip = try! IPAddress.parse("192.0.2.0/24");
+    let host = try! IPAddress.parse("192.0.2.1");
+    XCTAssertEqual("::ffff:c000:200/120", ip.to_ipv6(Ipv6Embedding.mapped)!.to_string());
+    XCTAssertEqual("::ffff:192.0.2.1/32", host.to_ipv6(Ipv6Embedding.mapped)!.to_string_mapped());
+    XCTAssertEqual("::ffff:0:0/96", try! IPAddress.parse("0.0.0.0/0").to_ipv6(Ipv6Embedding.mapped)!.to_string());
+    XCTAssertEqual("::c000:200/120", ip.to_ipv6(Ipv6Embedding.compatible)!.to_string());
+    XCTAssertEqual("::c000:201/128", host.to_ipv6(Ipv6Embedding.compatible)!.to_string());
+    XCTAssertEqual("2002:c000:200::/40", ip.to_ipv6(Ipv6Embedding.six_to_four)!.to_string());
+    XCTAssertEqual("2002:c000:201::/48", host.to_ipv6(Ipv6Embedding.six_to_four)!.to_string());
+    XCTAssertEqual("64:ff9b::c000:200/120",
+                   ip.to_ipv6(Ipv6Embedding.nat64(IPAddress.nat64_well_known_prefix))!.to_string());
+    XCTAssertEqual("2001:db8:c000:200::/56",
+                   ip.to_ipv6(Ipv6Embedding.nat64(try! IPAddress.parse("2001:db8::/32")))!.to_string());
+    XCTAssertNil(ip.to_ipv6(Ipv6Embedding.nat64(try! IPAddress.parse("2001:db8::/36"))));
+    let ip6 = try! IPAddress.parse("2001:db8::1/64");
+    XCTAssertEqual(ip6, ip6.to_ipv6(Ipv6Embedding.mapped));
+  }
+  
   func test_method_reverse() {
     XCTAssertEqual(setup().ip.dns_reverse(), "10.16.172.in-addr.arpa");
   }
   
   func test_method_dns_rev_domains() {
-    XCTAssertEqual(IPAddress.parse("173.17.5.1/23")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("173.17.5.1/23").dns_rev_domains(),
                    ["4.17.173.in-addr.arpa", "5.17.173.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("173.17.1.1/15")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("173.17.1.1/15").dns_rev_domains(),
                    ["16.173.in-addr.arpa", "17.173.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("173.17.1.1/7")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("173.17.1.1/7").dns_rev_domains(),
                    ["172.in-addr.arpa", "173.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("173.17.1.1/29")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("173.17.1.1/29").dns_rev_domains(),
                    [
                     "0.1.17.173.in-addr.arpa",
                     "1.1.17.173.in-addr.arpa",
@@ -328,23 +377,23 @@ class Ipv4Tests : XCTestCase {
                     "6.1.17.173.in-addr.arpa",
                     "7.1.17.173.in-addr.arpa"
       ]);
-    XCTAssertEqual(IPAddress.parse("174.17.1.1/24")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("174.17.1.1/24").dns_rev_domains(),
                    ["1.17.174.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("175.17.1.1/16")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("175.17.1.1/16").dns_rev_domains(),
                    ["17.175.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("176.17.1.1/8")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("176.17.1.1/8").dns_rev_domains(),
                    ["176.in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("177.17.1.1/0")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("177.17.1.1/0").dns_rev_domains(),
                    ["in-addr.arpa"]);
-    XCTAssertEqual(IPAddress.parse("178.17.1.1/32")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("178.17.1.1/32").dns_rev_domains(),
                    ["1.1.17.178.in-addr.arpa"]);
   }
   
   func test_method_compare() {
-    var ip1 = IPAddress.parse("10.1.1.1/8")!;
-    var ip2 = IPAddress.parse("10.1.1.1/16")!;
-    var ip3 = IPAddress.parse("172.16.1.1/14")!;
-    let ip4 = IPAddress.parse("10.1.1.1/8")!;
+    var ip1 = try! IPAddress.parse("10.1.1.1/8");
+    var ip2 = try! IPAddress.parse("10.1.1.1/16");
+    var ip3 = try! IPAddress.parse("172.16.1.1/14");
+    let ip4 = try! IPAddress.parse("10.1.1.1/8");
     
     // ip2 should be greater than ip1
     XCTAssertEqual(true, ip1.lt(ip2));
@@ -365,9 +414,9 @@ class Ipv4Tests : XCTestCase {
     let res = [ip1, ip2, ip3].sorted(by: { $0.lt($1) })
     XCTAssertEqual(IPAddress.to_string_vec(res), ["10.1.1.1/8", "10.1.1.1/16", "172.16.1.1/14"]);
     // test same prefix
-    ip1 = IPAddress.parse("10.0.0.0/24")!;
-    ip2 = IPAddress.parse("10.0.0.0/16")!;
-    ip3 = IPAddress.parse("10.0.0.0/8")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/24");
+    ip2 = try! IPAddress.parse("10.0.0.0/16");
+    ip3 = try! IPAddress.parse("10.0.0.0/8");
     
     let rres = [ip1, ip2, ip3].sorted(by: { $0.lt($1) })
     XCTAssertEqual(IPAddress.to_string_vec(rres), ["10.0.0.0/8", "10.0.0.0/16", "10.0.0.0/24"]);
@@ -375,43 +424,43 @@ class Ipv4Tests : XCTestCase {
   }
   
   func test_method_minus() {
-    let ip1 = IPAddress.parse("10.1.1.1/8")!;
-    let ip2 = IPAddress.parse("10.1.1.10/8")!;
+    let ip1 = try! IPAddress.parse("10.1.1.1/8");
+    let ip2 = try! IPAddress.parse("10.1.1.10/8");
     XCTAssertEqual("9", String(ip2.sub(ip1)));
     XCTAssertEqual("9", String(ip1.sub(ip2)));
   }
   
   func test_method_plus() {
-    var ip1 = IPAddress.parse("172.16.10.1/24")!;
-    var ip2 = IPAddress.parse("172.16.11.2/24")!;
+    var ip1 = try! IPAddress.parse("172.16.10.1/24");
+    var ip2 = try! IPAddress.parse("172.16.11.2/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)), ["172.16.10.0/23"]);
     
-    ip2 = IPAddress.parse("172.16.12.2/24")!;
+    ip2 = try! IPAddress.parse("172.16.12.2/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)),
                    [ip1.network().to_string(), ip2.network().to_string()]);
     
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.0.2.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.0.2.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)),
                    ["10.0.0.0/23", "10.0.2.0/24"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.0.2.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.0.2.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)),
                    ["10.0.0.0/23", "10.0.2.0/24"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/16")!;
-    ip2 = IPAddress.parse("10.0.2.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/16");
+    ip2 = try! IPAddress.parse("10.0.2.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)), ["10.0.0.0/16"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.1.0.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.1.0.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(ip1.add(ip2)),
                    ["10.0.0.0/23", "10.1.0.0/24"]);
   }
   
   func test_method_netmask_equal() {
-    let ip = IPAddress.parse("10.1.1.1/16")!;
+    let ip = try! IPAddress.parse("10.1.1.1/16");
     XCTAssertEqual(16, ip.prefix.num);
     let ip2 = ip.change_netmask("255.255.255.0")!;
     XCTAssertEqual(24, ip2.prefix.num);
@@ -503,98 +552,99 @@ class Ipv4Tests : XCTestCase {
     XCTAssertEqual(IPAddress.summarize([s.ip])!, [s.ip.network()]);
     
     // Summarize homogeneous networks
-    var ip1 = IPAddress.parse("172.16.10.1/24")!;
-    var ip2 = IPAddress.parse("172.16.11.2/24")!;
+    var ip1 = try! IPAddress.parse("172.16.10.1/24");
+    var ip2 = try! IPAddress.parse("172.16.11.2/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2])),
                    ["172.16.10.0/23"]);
     
-    ip1 = IPAddress.parse("10.0.0.1/24")!;
-    ip2 = IPAddress.parse("10.0.1.1/24")!;
-    var ip3 = IPAddress.parse("10.0.2.1/24")!;
-    var ip4 = IPAddress.parse("10.0.3.1/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.1/24");
+    ip2 = try! IPAddress.parse("10.0.1.1/24");
+    var ip3 = try! IPAddress.parse("10.0.2.1/24");
+    var ip4 = try! IPAddress.parse("10.0.3.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2, ip3, ip4])),
                    ["10.0.0.0/22"]);
-    ip1 = IPAddress.parse("10.0.0.1/24")!;
-    ip2 = IPAddress.parse("10.0.1.1/24")!;
-    ip3 = IPAddress.parse("10.0.2.1/24")!;
-    ip4 = IPAddress.parse("10.0.3.1/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.1/24");
+    ip2 = try! IPAddress.parse("10.0.1.1/24");
+    ip3 = try! IPAddress.parse("10.0.2.1/24");
+    ip4 = try! IPAddress.parse("10.0.3.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip4, ip3, ip2, ip1])),
                    ["10.0.0.0/22"]);
     
     // Summarize non homogeneous networks
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.0.2.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.0.2.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2])),
                    ["10.0.0.0/23", "10.0.2.0/24"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/16")!;
-    ip2 = IPAddress.parse("10.0.2.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/16");
+    ip2 = try! IPAddress.parse("10.0.2.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2])),
                    ["10.0.0.0/16"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.1.0.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.1.0.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2])),
                    ["10.0.0.0/23", "10.1.0.0/24"]);
     
-    ip1 = IPAddress.parse("10.0.0.0/23")!;
-    ip2 = IPAddress.parse("10.0.2.0/23")!;
-    ip3 = IPAddress.parse("10.0.4.0/24")!;
-    ip4 = IPAddress.parse("10.0.6.0/24")!;
+    ip1 = try! IPAddress.parse("10.0.0.0/23");
+    ip2 = try! IPAddress.parse("10.0.2.0/23");
+    ip3 = try! IPAddress.parse("10.0.4.0/24");
+    ip4 = try! IPAddress.parse("10.0.6.0/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2, ip3, ip4])),
                    ["10.0.0.0/22", "10.0.4.0/24", "10.0.6.0/24"]);
     
-    ip1 = IPAddress.parse("10.0.1.1/24")!;
-    ip2 = IPAddress.parse("10.0.2.1/24")!;
-    ip3 = IPAddress.parse("10.0.3.1/24")!;
-    ip4 = IPAddress.parse("10.0.4.1/24")!;
+    ip1 = try! IPAddress.parse("10.0.1.1/24");
+    ip2 = try! IPAddress.parse("10.0.2.1/24");
+    ip3 = try! IPAddress.parse("10.0.3.1/24");
+    ip4 = try! IPAddress.parse("10.0.4.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2, ip3, ip4])),
                    ["10.0.1.0/24", "10.0.2.0/23", "10.0.4.0/24"]);
     
     
-    ip1 = IPAddress.parse("10.0.1.1/24")!;
-    ip2 = IPAddress.parse("10.0.2.1/24")!;
-    ip3 = IPAddress.parse("10.0.3.1/24")!;
-    ip4 = IPAddress.parse("10.0.4.1/24")!;
+    ip1 = try! IPAddress.parse("10.0.1.1/24");
+    ip2 = try! IPAddress.parse("10.0.2.1/24");
+    ip3 = try! IPAddress.parse("10.0.3.1/24");
+    ip4 = try! IPAddress.parse("10.0.4.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip4, ip3, ip2, ip1])),
                    ["10.0.1.0/24", "10.0.2.0/23", "10.0.4.0/24"]);
     
     
-    ip1 = IPAddress.parse("10.0.1.1/24")!;
-    ip2 = IPAddress.parse("10.10.2.1/24")!;
-    ip3 = IPAddress.parse("172.16.0.1/24")!;
-    ip4 = IPAddress.parse("172.16.1.1/24")!;
+    ip1 = try! IPAddress.parse("10.0.1.1/24");
+    ip2 = try! IPAddress.parse("10.10.2.1/24");
+    ip3 = try! IPAddress.parse("172.16.0.1/24");
+    ip4 = try! IPAddress.parse("172.16.1.1/24");
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize([ip1, ip2, ip3, ip4])),
                    ["10.0.1.0/24", "10.10.2.0/24", "172.16.0.0/23"]);
     
-    var ips = [IPAddress.parse("10.0.0.12/30")!,
-               IPAddress.parse("10.0.100.0/24")!];
+    var ips = [try! IPAddress.parse("10.0.0.12/30"),
+               try! IPAddress.parse("10.0.100.0/24")];
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize(ips)),
                    ["10.0.0.12/30", "10.0.100.0/24"]);
     
-    ips = [IPAddress.parse("172.16.0.0/31")!,
-           IPAddress.parse("10.10.2.1/32")!];
+    ips = [try! IPAddress.parse("172.16.0.0/31"),
+           try! IPAddress.parse("10.10.2.1/32")];
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize(ips)),
                    ["10.10.2.1/32", "172.16.0.0/31"]);
     
-    ips = [IPAddress.parse("172.16.0.0/32")!, IPAddress.parse("10.10.2.1/32")!];
+    ips = [try! IPAddress.parse("172.16.0.0/32"), try! IPAddress.parse("10.10.2.1/32")];
     XCTAssertEqual(IPAddress.to_string_vec(IPAddress.summarize(ips)),
                    ["10.10.2.1/32", "172.16.0.0/32"]);
   }
   
   func test_classmethod_parse_classful() {
     for (ip, prefix) in setup().classful {
-      let res = Ipv4.parse_classful(ip)!;
+      let res = try! Ipv4.parse_classful(ip);
       XCTAssertEqual(prefix, res.prefix.num);
       XCTAssertEqual("\(ip)/\(prefix)", res.to_string());
     }
-    XCTAssertNil(Ipv4.parse_classful("192.168.256.257"));
+    XCTAssertThrowsError(try Ipv4.parse_classful("192.168.256.257"));
   }
   
   static var allTests : [(String, (Ipv4Tests) -> () throws -> Void)] {
     return [
       ("test_initialize", test_initialize),
       ("test_initialize_format_error", test_initialize_format_error),
+      ("test_initialize_strict", test_initialize_strict),
       ("test_initialize_without_prefix", test_initialize_without_prefix),
       ("test_attributes", test_attributes),
       ("test_octets", test_octets),
@@ -624,6 +674,7 @@ class Ipv4Tests : XCTestCase {
       ("test_method_b", test_method_b),
       ("test_method_c", test_method_c),
       ("test_method_to_ipv6", test_method_to_ipv6),
+      ("test_method_to_ipv6_embedding", test_method_to_ipv6_embedding),
       ("test_method_reverse", test_method_reverse),
       ("test_method_dns_rev_domains", test_method_dns_rev_domains),
       ("test_method_compare", test_method_compare),
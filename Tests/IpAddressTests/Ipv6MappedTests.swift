@@ -28,7 +28,7 @@ class Ipv6MappedTests: XCTestCase {
   
   func setup()-> IPv6MappedTest {
     let ipv6 = IPv6MappedTest(
-      ip: Ipv6Mapped.create("::172.16.10.1")!,
+      ip: try! Ipv6Mapped.create("::172.16.10.1"),
       s: "::ffff:172.16.10.1",
       sstr: "::ffff:172.16.10.1/32",
       string: "0000:0000:0000:0000:0000:ffff:ac10:0a01/128",
@@ -49,21 +49,21 @@ class Ipv6MappedTests: XCTestCase {
   
   func test_initialize() {
     let s = setup();
-    XCTAssertNotNil(IPAddress.parse("::172.16.10.1")!);
+    XCTAssertNoThrow(try IPAddress.parse("::172.16.10.1"));
     for (ip, u128) in s.valid_mapped {
       // println("-{}--{}", ip, u128);
-      XCTAssertNotNil(IPAddress.parse(ip));
-      XCTAssertEqual(u128, IPAddress.parse(ip)!.host_address);
+      XCTAssertNoThrow(try IPAddress.parse(ip));
+      XCTAssertEqual(u128, try! IPAddress.parse(ip).host_address);
     }
     for (ip, u128) in s.valid_mapped_ipv6 {
       // println("===={}=={:x}", ip, u128);
-      XCTAssertNotNil(IPAddress.parse(ip));
-      XCTAssertEqual(u128, IPAddress.parse(ip)!.host_address);
+      XCTAssertNoThrow(try IPAddress.parse(ip));
+      XCTAssertEqual(u128, try! IPAddress.parse(ip).host_address);
     }
   }
   func test_mapped_from_ipv6_conversion() {
     for (ip6, ip4) in setup().valid_mapped_ipv6_conversion {
-      XCTAssertEqual(ip4, IPAddress.parse(ip6)!.mapped!.to_s());
+      XCTAssertEqual(ip4, try! IPAddress.parse(ip6).mapped!.to_s());
     }
   }
   func test_attributes() {
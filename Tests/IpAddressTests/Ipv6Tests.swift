@@ -9,8 +9,8 @@ class IPv6Test {
   var valid_ipv6 = [String: BigUInt]();
   let invalid_ipv6 = [":1:2:3:4:5:6:7", ":1:2:3:4:5:6:7", "2002:516:2:200", "dd"];
   var networks = [String: String]();
-  let ip: IPAddress = IPAddress.parse("2001:db8::8:800:200c:417a/64")!;
-  let network: IPAddress = IPAddress.parse("2001:db8:8:800::/64")!;
+  let ip: IPAddress = try! IPAddress.parse("2001:db8::8:800:200c:417a/64");
+  let network: IPAddress = try! IPAddress.parse("2001:db8:8:800::/64");
   let arr : [UInt]  = [8193, 3512, 0, 0, 8, 2048, 8204, 16762];
   let hex: String = "20010db80000000000080800200c417a";
 }
@@ -59,14 +59,14 @@ class Ipv6Tests : XCTestCase {
     XCTAssertEqual(false, setup().ip.is_ipv4());
     
     for (ip, _) in setup().valid_ipv6 {
-      XCTAssertNotNil(IPAddress.parse(ip));
+      XCTAssertNoThrow(try IPAddress.parse(ip));
     }
     for ip in setup().invalid_ipv6 {
-      XCTAssertNil(IPAddress.parse(ip));
+      XCTAssertThrowsError(try IPAddress.parse(ip));
     }
     XCTAssertEqual(64, setup().ip.prefix.num);
     
-    XCTAssertNotNil(IPAddress.parse("::10.1.1.1"));
+    XCTAssertNoThrow(try IPAddress.parse("::10.1.1.1"));
   }
   func test_attribute_groups() {
     XCTAssertEqual(setup().arr, setup().ip.parts())
@@ -78,11 +78,11 @@ class Ipv6Tests : XCTestCase {
   
   func test_method_to_i() {
     for (ip, num) in setup().valid_ipv6 {
-      XCTAssertEqual(num, IPAddress.parse(ip)!.host_address);
+      XCTAssertEqual(num, try! IPAddress.parse(ip).host_address);
     }
   }
   func test_method_set_prefix() {
-    let ip = IPAddress.parse("2001:db8::8:800:200c:417a")!;
+    let ip = try! IPAddress.parse("2001:db8::8:800:200c:417a");
     XCTAssertEqual(128, ip.prefix.num);
     XCTAssertEqual("2001:db8::8:800:200c:417a/128", ip.to_string());
     let nip = ip.change_prefix(64)!;
@@ -91,7 +91,7 @@ class Ipv6Tests : XCTestCase {
   }
   func test_method_mapped() {
     XCTAssertEqual(false, setup().ip.is_mapped());
-    let ip6 = IPAddress.parse("::ffff:1234:5678")!;
+    let ip6 = try! IPAddress.parse("::ffff:1234:5678");
     XCTAssertEqual(true, ip6.is_mapped());
   }
   func test_method_group() {
@@ -115,31 +115,31 @@ class Ipv6Tests : XCTestCase {
     XCTAssertNotNil(Ipv6.from_int(BigUInt("42540766411282592875350729025363378175"), 64)!.eq(setup().ip.broadcast()));
   }
   func test_method_size() {
-    var ip = IPAddress.parse("2001:db8::8:800:200c:417a/64")!;
+    var ip = try! IPAddress.parse("2001:db8::8:800:200c:417a/64");
     XCTAssertEqual(BigUInt(1)<<64, ip.size());
-    ip = IPAddress.parse("2001:db8::8:800:200c:417a/32")!;
+    ip = try! IPAddress.parse("2001:db8::8:800:200c:417a/32");
     XCTAssertEqual(BigUInt(1)<<96, ip.size());
-    ip = IPAddress.parse("2001:db8::8:800:200c:417a/120")!;
+    ip = try! IPAddress.parse("2001:db8::8:800:200c:417a/120");
     XCTAssertEqual(BigUInt(1)<<8, ip.size());
-    ip = IPAddress.parse("2001:db8::8:800:200c:417a/124")!;
+    ip = try! IPAddress.parse("2001:db8::8:800:200c:417a/124");
     XCTAssertEqual(BigUInt(1)<<4, ip.size());
   }
   func test_method_includes() {
     let ip = setup().ip;
     XCTAssertEqual(true, ip.includes(ip));
     // test prefix on same address
-    var included = IPAddress.parse("2001:db8::8:800:200c:417a/128")!;
-    var not_included = IPAddress.parse("2001:db8::8:800:200c:417a/46")!;
+    var included = try! IPAddress.parse("2001:db8::8:800:200c:417a/128");
+    var not_included = try! IPAddress.parse("2001:db8::8:800:200c:417a/46");
     XCTAssertEqual(true, ip.includes(included));
     XCTAssertEqual(false, ip.includes(not_included));
     // test address on same prefix
-    included = IPAddress.parse("2001:db8::8:800:200c:0/64")!;
-    not_included = IPAddress.parse("2001:db8:1::8:800:200c:417a/64")!;
+    included = try! IPAddress.parse("2001:db8::8:800:200c:0/64");
+    not_included = try! IPAddress.parse("2001:db8:1::8:800:200c:417a/64");
     XCTAssertEqual(true, ip.includes(included));
     XCTAssertEqual(false, ip.includes(not_included));
     // general test
-    included = IPAddress.parse("2001:db8::8:800:200c:1/128")!;
-    not_included = IPAddress.parse("2001:db8:1::8:800:200c:417a/76")!;
+    included = try! IPAddress.parse("2001:db8::8:800:200c:1/128");
+    not_included = try! IPAddress.parse("2001:db8:1::8:800:200c:417a/76");
     XCTAssertEqual(true, ip.includes(included));
     XCTAssertEqual(false, ip.includes(not_included));
   }
@@ -164,18 +164,18 @@ class Ipv6Tests : XCTestCase {
   func test_method_reverse() {
     let str = "f.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.2.0.0.0.5.0.5.0.e.f.f.3.ip6.arpa";
     XCTAssertEqual(str,
-                   IPAddress.parse("3ffe:505:2::f")!.dns_reverse());
+                   try! IPAddress.parse("3ffe:505:2::f").dns_reverse());
   }
   
   func test_method_dns_rev_domains() {
-    XCTAssertEqual(IPAddress.parse("f000:f100::/3")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("f000:f100::/3").dns_rev_domains(),
                    ["e.ip6.arpa", "f.ip6.arpa"]);
-    XCTAssertEqual(IPAddress.parse("fea3:f120::/15")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("fea3:f120::/15").dns_rev_domains(),
                    ["2.a.e.f.ip6.arpa", "3.a.e.f.ip6.arpa"]);
-    XCTAssertEqual(IPAddress.parse("3a03:2f80:f::/48")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("3a03:2f80:f::/48").dns_rev_domains(),
                    ["f.0.0.0.0.8.f.2.3.0.a.3.ip6.arpa"]);
     
-    XCTAssertEqual(IPAddress.parse("f000:f100::1234/125")!.dns_rev_domains(),
+    XCTAssertEqual(try! IPAddress.parse("f000:f100::1234/125").dns_rev_domains(),
                    ["0.3.2.1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.1.f.0.0.0.f.ip6.arpa",
                     "1.3.2.1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.1.f.0.0.0.f.ip6.arpa",
                     "2.3.2.1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.1.f.0.0.0.f.ip6.arpa",
@@ -188,39 +188,39 @@ class Ipv6Tests : XCTestCase {
   
   func test_method_compressed() {
     XCTAssertEqual("1:1:1::1",
-                   IPAddress.parse("1:1:1:0:0:0:0:1")!.to_s());
+                   try! IPAddress.parse("1:1:1:0:0:0:0:1").to_s());
     XCTAssertEqual("1:0:1::1",
-                   IPAddress.parse("1:0:1:0:0:0:0:1")!.to_s());
+                   try! IPAddress.parse("1:0:1:0:0:0:0:1").to_s());
     XCTAssertEqual("1::1:1:1:2:3:1",
-                   IPAddress.parse("1:0:1:1:1:2:3:1")!.to_s());
+                   try! IPAddress.parse("1:0:1:1:1:2:3:1").to_s());
     XCTAssertEqual("1::1:1:0:2:3:1",
-                   IPAddress.parse("1:0:1:1::2:3:1")!.to_s());
+                   try! IPAddress.parse("1:0:1:1::2:3:1").to_s());
     XCTAssertEqual("1:0:0:1::1",
-                   IPAddress.parse("1:0:0:1:0:0:0:1")!.to_s());
+                   try! IPAddress.parse("1:0:0:1:0:0:0:1").to_s());
     XCTAssertEqual("1::1:0:0:1",
-                   IPAddress.parse("1:0:0:0:1:0:0:1")!.to_s());
-    XCTAssertEqual("1::1", IPAddress.parse("1:0:0:0:0:0:0:1")!.to_s());
-    // XCTAssertEqual("1:1.1:2:0:0:1", IPAddress.parse("1:1:0:1:2.1")!.to_s
+                   try! IPAddress.parse("1:0:0:0:1:0:0:1").to_s());
+    XCTAssertEqual("1::1", try! IPAddress.parse("1:0:0:0:0:0:0:1").to_s());
+    // XCTAssertEqual("1:1.1:2:0:0:1", try! IPAddress.parse("1:1:0:1:2.1").to_s
   }
   
   func test_method_unspecified() {
-    XCTAssertEqual(true, IPAddress.parse("::")!.is_unspecified());
+    XCTAssertEqual(true, try! IPAddress.parse("::").is_unspecified());
     XCTAssertEqual(false, setup().ip.is_unspecified());
   }
   
   func test_method_loopback() {
-    XCTAssertEqual(true, IPAddress.parse("::1")!.is_loopback());
+    XCTAssertEqual(true, try! IPAddress.parse("::1").is_loopback());
     XCTAssertEqual(false, setup().ip.is_loopback());
   }
   
   func test_method_network() {
     for (addr, net) in setup().networks {
-      let ip = IPAddress.parse(addr)!;
+      let ip = try! IPAddress.parse(addr);
       XCTAssertEqual(net, ip.network().to_string());
     }
   }
   func test_method_each() {
-    let ip = IPAddress.parse("2001:db8::4/125")!;
+    let ip = try! IPAddress.parse("2001:db8::4/125");
     var arr: [String] = [String]();
     ip.each({ arr.append($0.to_s()) });
     XCTAssertEqual(arr, ["2001:db8::", "2001:db8::1", "2001:db8::2", "2001:db8::3",
@@ -247,7 +247,7 @@ class Ipv6Tests : XCTestCase {
     for prefix in 0...128 {
       let nr_networks = 1 << ((128 - prefix) % 4);
       for adr in test_addrs {
-        let net_adr = IPAddress.parse("\(adr)/\(prefix)")!;
+        let net_adr = try! IPAddress.parse("\(adr)/\(prefix)");
         let ret = net_adr.dns_networks();
         XCTAssertEqual(ret[0].prefix.num % 4, 0);
         XCTAssertEqual(ret.count, nr_networks);
@@ -257,21 +257,21 @@ class Ipv6Tests : XCTestCase {
       }
     }
     var ret0 = [String]();
-    for i in IPAddress.parse("fd01:db8::4/3")!.dns_networks() {
+    for i in try! IPAddress.parse("fd01:db8::4/3").dns_networks() {
       ret0.append(i.to_string());
     }
     XCTAssertEqual(ret0, ["e000::/4", "f000::/4"]);
     var ret1 = [String]();
-    for i in IPAddress.parse("3a03:2f80:f::/48")!.dns_networks() {
+    for i in try! IPAddress.parse("3a03:2f80:f::/48").dns_networks() {
       ret1.append(i.to_string());
     }
     XCTAssertEqual(ret1, ["3a03:2f80:f::/48"]);
   }
   func test_method_compare() {
-    let ip1 = IPAddress.parse("2001:db8:1::1/64")!;
-    let ip2 = IPAddress.parse("2001:db8:2::1/64")!;
-    let ip3 = IPAddress.parse("2001:db8:1::2/64")!;
-    let ip4 = IPAddress.parse("2001:db8:1::1/65")!;
+    let ip1 = try! IPAddress.parse("2001:db8:1::1/64");
+    let ip2 = try! IPAddress.parse("2001:db8:2::1/64");
+    let ip3 = try! IPAddress.parse("2001:db8:1::2/64");
+    let ip4 = try! IPAddress.parse("2001:db8:1::1/65");
     
     // ip2 should be greater than ip1
     XCTAssertEqual(true, ip2.gt(ip1));
@@ -310,24 +310,181 @@ class Ipv6Tests : XCTestCase {
   func test_classmethod_compress() {
     let compressed = "2001:db8:0:cd30::";
     let expanded = "2001:0db8:0000:cd30:0000:0000:0000:0000";
-    XCTAssertEqual(compressed, IPAddress.parse(expanded)!.to_s());
+    XCTAssertEqual(compressed, try! IPAddress.parse(expanded).to_s());
     XCTAssertEqual("2001:db8::cd3",
-                   IPAddress.parse("2001:0db8:0::cd3")!.to_s());
+                   try! IPAddress.parse("2001:0db8:0::cd3").to_s());
     XCTAssertEqual("2001:db8::cd30",
-                   IPAddress.parse("2001:0db8::cd30")!.to_s());
+                   try! IPAddress.parse("2001:0db8::cd30").to_s());
     XCTAssertEqual("2001:db8::cd3",
-                   IPAddress.parse("2001:0db8::cd3")!.to_s());
+                   try! IPAddress.parse("2001:0db8::cd3").to_s());
   }
   func test_classhmethod_parse_u128() {
     for (ip, num) in setup().valid_ipv6 {
       //console.log(">>>>>>>>", i);
-      XCTAssertEqual(IPAddress.parse(ip)!.to_s(), Ipv6.from_int(num, 128)!.to_s());
+      XCTAssertEqual(try! IPAddress.parse(ip).to_s(), Ipv6.from_int(num, 128)!.to_s());
     }
   }
   func test_classmethod_parse_hex() {
     XCTAssertEqual(setup().ip.to_string(),
                    Ipv6.from_str(setup().hex, 16, 64)!.to_string());
   }
+  func test_method_to_s_canonical() {
+    XCTAssertEqual("2001:db8::1:1:1:1:1", try! IPAddress.parse("2001:db8:0:1:1:1:1:1").to_s());
+    XCTAssertEqual("2001:db8:0:1:1:1:1:1", try! IPAddress.parse("2001:db8:0:1:1:1:1:1").to_s_canonical());
+    XCTAssertEqual("2001:db8::1:0:0:1", try! IPAddress.parse("2001:db8:0:0:1:0:0:1").to_s_canonical());
+    XCTAssertEqual("2001:0:0:1::1", try! IPAddress.parse("2001:0:0:1:0:0:0:1").to_s_canonical());
+    XCTAssertEqual("2001:db8::8:800:200c:417a/64", setup().ip.to_string_canonical());
+    XCTAssertEqual("::", try! IPAddress.parse("::").to_s_canonical());
+    XCTAssertEqual("::1", try! IPAddress.parse("::1").to_s_canonical());
+    XCTAssertEqual("1::", try! IPAddress.parse("1::").to_s_canonical());
+    XCTAssertEqual("1:0:1:0:1:0:1:0", try! IPAddress.parse("1:0:1:0:1:0:1:0").to_s_canonical());
+    XCTAssertEqual("fe80::1%eth0/64", try! IPAddress.parse("FE80:0::1%eth0/64").to_string_canonical());
+    XCTAssertEqual("10.0.0.1/8", try! IPAddress.parse("10.0.0.1/255.0.0.0").to_string_canonical());
+  }
+  func test_classmethod_is_canonical() {
+    XCTAssertTrue(IPAddress.is_canonical("2001:db8::1"));
+    XCTAssertTrue(IPAddress.is_canonical("2001:db8::1/64"));
+    XCTAssertTrue(IPAddress.is_canonical("2001:db8:0:1:1:1:1:1"));
+    XCTAssertTrue(IPAddress.is_canonical("2001:db8::1:0:0:1"));
+    XCTAssertTrue(IPAddress.is_canonical("fe80::1%eth0"));
+    XCTAssertTrue(IPAddress.is_canonical("10.0.0.1/8"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:DB8::1"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:0db8::1"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:db8::1:1:1:1:1"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:db8:0:0:1::1"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:db8:0:0:0:0:0:1"));
+    XCTAssertFalse(IPAddress.is_canonical("::ffff:10.0.0.1"));
+    XCTAssertFalse(IPAddress.is_canonical("10.1"));
+    XCTAssertFalse(IPAddress.is_canonical("010.0.0.1"));
+    XCTAssertFalse(IPAddress.is_canonical("10.0.0.1/255.0.0.0"));
+    XCTAssertFalse(IPAddress.is_canonical(" 2001:db8::1"));
+    XCTAssertFalse(IPAddress.is_canonical("2001:db8::g"));
+  }
+  func test_zone() {
+    let ip = try! IPAddress.parse("fe80::1%eth0/64");
+    XCTAssertEqual("eth0", ip.zone);
+    XCTAssertEqual("fe80::1%eth0", ip.to_s());
+    XCTAssertEqual("fe80::1%eth0/64", ip.to_string());
+    XCTAssertEqual("fe80:0000:0000:0000:0000:0000:0000:0001%eth0/64", ip.to_string_uncompressed());
+    XCTAssertEqual("fe80::1%2/64", try! IPAddress.parse("fe80::1%2/64").to_string());
+    XCTAssertEqual("eth0.100", try! IPAddress.parse("fe80::1%eth0.100").zone);
+    XCTAssertEqual(ip, ip.clone());
+    XCTAssertEqual(ip, try! IPAddress.parse("fe80::1%eth0/64"));
+    XCTAssertNotEqual(ip, try! IPAddress.parse("fe80::1/64"));
+    XCTAssertNotEqual(ip, try! IPAddress.parse("fe80::1%eth1/64"));
+    XCTAssertEqual(ip, try! IPAddress.parse("fe80::1/64").with_zone("eth0"));
+    XCTAssertNil(ip.with_zone(nil).zone);
+    XCTAssertThrowsError(try IPAddress.parse("fe80::1%")) { err in
+      XCTAssertEqual(IPAddressParseError.invalid_zone("", 8), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("fe80::1%a%b"));
+  }
+  func test_zone_strict() {
+    let strict = ParseOptions.strict;
+    XCTAssertNoThrow(try IPAddress.parse("2001:db8::1%eth0"));
+    XCTAssertNoThrow(try IPAddress.parse("169.254.0.1%eth0"));
+    XCTAssertNoThrow(try IPAddress.parse("fe80::1%eth0/64", strict));
+    XCTAssertNoThrow(try IPAddress.parse("febf::1%eth0", strict));
+    XCTAssertNoThrow(try IPAddress.parse("ff02::1%eth0", strict));
+    XCTAssertThrowsError(try IPAddress.parse("2001:db8::1%eth0", strict)) { err in
+      XCTAssertEqual(IPAddressParseError.zone_not_allowed("eth0", 12), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("fec0::1%eth0", strict));
+    XCTAssertThrowsError(try IPAddress.parse("169.254.0.1%eth0", strict));
+    XCTAssertThrowsError(try IPAddress.parse("::ffff:169.254.0.1%eth0", strict));
+  }
+  func test_zone_network() {
+    let ip = try! IPAddress.parse("fe80::1:2%eth0/64");
+    XCTAssertEqual("fe80::%eth0/64", ip.network().to_string());
+    XCTAssertEqual("fe80::ffff:ffff:ffff:ffff%eth0/64", ip.broadcast().to_string());
+    XCTAssertEqual("fe80::1:2%eth0/112", ip.change_prefix(112)!.to_string());
+    XCTAssertNil(ip.netmask().zone);
+    XCTAssertTrue(ip.network().includes(ip));
+    XCTAssertFalse(ip.network().includes(try! IPAddress.parse("fe80::1:2%eth1/64")));
+    XCTAssertFalse(ip.network().includes(try! IPAddress.parse("fe80::1:2/64")));
+    XCTAssertTrue(try! IPAddress.parse("fe80::/10").includes(ip));
+    XCTAssertEqual(["fe80::%eth0/63", "fe80::%eth1/64"],
+                   IPAddress.to_string_vec(IPAddress.summarize_str(["fe80::%eth0/64",
+                                                                    "fe80::%eth1/64",
+                                                                    "fe80:0:0:1::%eth0/64"])));
+  }
+  func test_method_subnets() {
+    let ip = try! IPAddress.parse("2001:db8::/32");
+    let subnets = ip.subnets(64)!;
+    XCTAssertEqual(BigUInt(1) << 32, subnets.size());
+    XCTAssertEqual("2001:db8::/64", subnets[0].to_string());
+    XCTAssertEqual("2001:db8:0:1::/64", subnets[1].to_string());
+    XCTAssertEqual("2001:db8:ffff:ffff::/64", subnets[-1].to_string());
+    XCTAssertEqual(["2001:db8::/64", "2001:db8:0:1::/64"], IPAddress.to_string_vec(Array(subnets.prefix(2))));
+    XCTAssertEqual(["2001:db8:ffff:ffff::/64", "2001:db8:ffff:fffe::/64"],
+                   IPAddress.to_string_vec(Array(subnets.reversed().prefix(2))));
+    XCTAssertNil(ip.subnets(31));
+    XCTAssertNil(ip.subnets(129));
+    XCTAssertEqual(["2001:db8::/32"], IPAddress.to_string_vec(ip.subnet(32)));
+    let all = try! IPAddress.parse("::/0");
+    XCTAssertEqual(BigUInt(1) << 128, all.subnets(128)!.size());
+    XCTAssertEqual("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128", all.subnets(128)![-1].to_string());
+    XCTAssertEqual("8000::/1", all.subnets(1)![1].to_string());
+    XCTAssertEqual("fe80:0:0:2::%eth0/64", try! IPAddress.parse("fe80::%eth0/10").subnets(64)![2].to_string());
+  }
+  
+  func test_method_subnet_at() {
+    let ip = try! IPAddress.parse("2001:db8:1::/48");
+    XCTAssertEqual("2001:db8:1:1267::/64", ip.subnet(at: 4711, prefix: 64)!.to_string());
+    XCTAssertEqual("2001:db8:1::/64", ip.subnet(at: 0, prefix: 64)!.to_string());
+    XCTAssertEqual("2001:db8:1:ffff::/64", ip.subnet(at: 65535, prefix: 64)!.to_string());
+    XCTAssertNil(ip.subnet(at: 65536, prefix: 64));
+    XCTAssertNil(ip.subnet(at: 0, prefix: 47));
+    XCTAssertEqual("2001:db8:1::1/128", ip.subnet(at: 1, prefix: 128)!.to_string());
+    XCTAssertEqual("10.0.0.192/26", try! IPAddress.parse("10.0.0.0/24").subnet(at: 3, prefix: 26)!.to_string());
+    XCTAssertEqual(UInt128(4711), ip.index(of: try! IPAddress.parse("2001:db8:1:1267::/64")));
+    XCTAssertEqual(UInt128(4711), ip.index(of: try! IPAddress.parse("2001:db8:1:1267::1/64")));
+    XCTAssertEqual(UInt128(0), ip.index(of: ip));
+    XCTAssertNil(ip.index(of: try! IPAddress.parse("2001:db8:2::/64")));
+    XCTAssertNil(ip.index(of: try! IPAddress.parse("2001:db8::/32")));
+    XCTAssertNil(ip.index(of: try! IPAddress.parse("10.0.0.0/24")));
+    for idx in [UInt128(0), UInt128(1), UInt128(4711), UInt128(65535)] {
+      XCTAssertEqual(idx, ip.index(of: ip.subnet(at: idx, prefix: 64)!));
+    }
+    let all = try! IPAddress.parse("::/0");
+    XCTAssertEqual(UInt128.max, all.index(of: try! IPAddress.parse("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")));
+  }
+  
+  func test_method_to_ipv4() {
+    let strs = [
+      ("::ffff:192.0.2.1", "192.0.2.1/32"),
+      ("::ffff:c000:200/120", "192.0.2.0/24"),
+      ("::c000:201", "192.0.2.1/32"),
+      ("::c000:200/120", "192.0.2.0/24"),
+      ("2002:c000:201::/48", "192.0.2.1/32"),
+      ("2002:c000:201:1::1/64", "192.0.2.1/32"),
+      ("2002:c000:200::/40", "192.0.2.0/24"),
+      ("64:ff9b::192.0.2.1", "192.0.2.1/32"),
+      ("64:ff9b::c000:200/120", "192.0.2.0/24"),
+    ];
+    for (ip6, ip4) in strs {
+      XCTAssertEqual(ip4, try! IPAddress.parse(ip6).to_ipv4()!.to_string(), ip6);
+    }
+    XCTAssertEqual("192.0.2.0/24", try! IPAddress.parse("2001:db8:c000:200::/56")
+                     .to_ipv4(try! IPAddress.parse("2001:db8::/32"))!.to_string());
+    XCTAssertNil(try! IPAddress.parse("2001:db8:c000:200::/56").to_ipv4());
+    XCTAssertNil(try! IPAddress.parse("::c000:200/64").to_ipv4());
+    XCTAssertNil(try! IPAddress.parse("::1").to_ipv4());
+    XCTAssertNil(try! IPAddress.parse("::").to_ipv4());
+    XCTAssertNil(try! IPAddress.parse("2001:db8::1").to_ipv4());
+    XCTAssertEqual("10.0.0.1/8", try! IPAddress.parse("10.0.0.1/8").to_ipv4()!.to_string());
+    for ip4 in ["0.0.0.0/0", "10.0.0.0/8", "192.0.2.0/24", "192.0.2.1/32"] {
+      let ip = try! IPAddress.parse(ip4);
+      for embedding in [Ipv6Embedding.mapped, Ipv6Embedding.compatible, Ipv6Embedding.six_to_four,
+                        Ipv6Embedding.nat64(IPAddress.nat64_well_known_prefix)] {
+        if (embedding == Ipv6Embedding.compatible && ip.address.u32 <= 1) {
+          continue;
+        }
+        XCTAssertEqual(ip, ip.to_ipv6(embedding)!.to_ipv4(), ip4);
+      }
+    }
+  }
+  
   static var allTests : [(String, (Ipv6Tests) -> () throws -> Void)] {
     return [
       ("test_attribute_address", test_attribute_address),
@@ -361,6 +518,14 @@ class Ipv6Tests : XCTestCase {
       ("test_classmethod_compress", test_classmethod_compress),
       ("test_classhmethod_parse_u128", test_classhmethod_parse_u128),
       ("test_classmethod_parse_hex", test_classmethod_parse_hex),
+      ("test_method_to_s_canonical", test_method_to_s_canonical),
+      ("test_classmethod_is_canonical", test_classmethod_is_canonical),
+      ("test_zone", test_zone),
+      ("test_zone_strict", test_zone_strict),
+      ("test_zone_network", test_zone_network),
+      ("test_method_subnets", test_method_subnets),
+      ("test_method_subnet_at", test_method_subnet_at),
+      ("test_method_to_ipv4", test_method_to_ipv4),
     ]
   }
 }
@@ -0,0 +1,97 @@
+import XCTest
+@testable import IpAddress
+
+class MulticastTests : XCTestCase {
+
+  func test_method_multicast_scope() {
+    XCTAssertEqual(MulticastScope.interface_local, ip("ff01::1").multicast_scope());
+    XCTAssertEqual(MulticastScope.link_local, ip("ff02::1").multicast_scope());
+    XCTAssertEqual(MulticastScope.realm_local, ip("ff03::fc").multicast_scope());
+    XCTAssertEqual(MulticastScope.admin_local, ip("ff14::1").multicast_scope());
+    XCTAssertEqual(MulticastScope.site_local, ip("ff05::1:3").multicast_scope());
+    XCTAssertEqual(MulticastScope.organization_local, ip("ff18::1").multicast_scope());
+    XCTAssertEqual(MulticastScope.global, ip("ff3e:30:2001:db8::1").multicast_scope());
+    XCTAssertNil(ip("ff00::1").multicast_scope());
+    XCTAssertNil(ip("ff06::1").multicast_scope());
+    XCTAssertNil(ip("2001:db8::1").multicast_scope());
+    XCTAssertEqual(MulticastScope.link_local, ip("224.0.0.251").multicast_scope());
+    XCTAssertEqual(MulticastScope.site_local, ip("239.255.255.250").multicast_scope());
+    XCTAssertEqual(MulticastScope.organization_local, ip("239.192.0.1").multicast_scope());
+    XCTAssertEqual(MulticastScope.admin_local, ip("239.1.2.3").multicast_scope());
+    XCTAssertEqual(MulticastScope.global, ip("232.1.1.1").multicast_scope());
+    XCTAssertNil(ip("10.0.0.1").multicast_scope());
+  }
+
+  func test_method_multicast_flags() {
+    XCTAssertEqual(MulticastFlags(rendezvous: false, prefix: false, transient: false), ip("ff02::1").multicast_flags());
+    XCTAssertEqual(MulticastFlags(rendezvous: false, prefix: false, transient: true), ip("ff15::1").multicast_flags());
+    XCTAssertEqual(MulticastFlags(rendezvous: false, prefix: true, transient: true), ip("ff3e::1").multicast_flags());
+    XCTAssertEqual(MulticastFlags(rendezvous: true, prefix: true, transient: true), ip("ff7e::1").multicast_flags());
+    XCTAssertNil(ip("224.0.0.1").multicast_flags());
+    XCTAssertNil(ip("fe80::1").multicast_flags());
+  }
+
+  func test_unicast_prefix_based() {
+    let group = IPAddress.multicast_from_prefix(ip("2001:db8:beef::/48"), 0x1234, MulticastScope.global)!;
+    XCTAssertEqual("ff3e:30:2001:db8:beef::1234/128", group.to_string());
+    XCTAssertEqual("2001:db8:beef::/48", group.unicast_prefix()!.to_string());
+    XCTAssertEqual(0x1234, group.multicast_group_id());
+    XCTAssertNil(group.rendezvous_point());
+    XCTAssertEqual("ff35:40:2001:db8:1:2:ffff:ffff", IPAddress.multicast_from_prefix(
+      ip("2001:db8:1:2::99/64"), 0xffffffff, MulticastScope.site_local)!.to_s());
+    XCTAssertNil(IPAddress.multicast_from_prefix(ip("2001:db8::/65"), 1, MulticastScope.global));
+    XCTAssertNil(IPAddress.multicast_from_prefix(ip("::/0"), 1, MulticastScope.global));
+    XCTAssertNil(IPAddress.multicast_from_prefix(ip("10.0.0.0/8"), 1, MulticastScope.global));
+    XCTAssertNil(ip("ff3e::8000:1").unicast_prefix());
+    XCTAssertNil(ip("ff3e::8000:1").multicast_group_id());
+    XCTAssertNil(ip("ff02::1").unicast_prefix());
+    XCTAssertNil(ip("2001:db8::1").unicast_prefix());
+  }
+
+  func test_embedded_rp() {
+    let group = IPAddress.multicast_embedded_rp(ip("2001:db8:beef:feed::1/64"), 0x1234, MulticastScope.global)!;
+    XCTAssertEqual("ff7e:140:2001:db8:beef:feed::1234", group.to_s());
+    XCTAssertEqual("2001:db8:beef:feed::1/64", group.rendezvous_point()!.to_string());
+    XCTAssertEqual("2001:db8:beef:feed::/64", group.unicast_prefix()!.to_string());
+    XCTAssertEqual(0x1234, group.multicast_group_id());
+    // RFC 3956 section 3.2
+    let example = ip("ff7e:320:2001:db8:beef:feed::1234");
+    XCTAssertEqual("2001:db8::3/32", example.rendezvous_point()!.to_string());
+    XCTAssertEqual("2001:db8:beef:feed::3/64", ip("ff7e:340:2001:db8:beef:feed::1234").rendezvous_point()!.to_string());
+    let rp = IPAddress.multicast_embedded_rp(ip("2001:db8::3/32"), 0x1234, MulticastScope.global)!;
+    XCTAssertEqual("ff7e:320:2001:db8::1234", rp.to_s());
+    XCTAssertEqual("2001:db8::3/32", rp.rendezvous_point()!.to_string());
+    XCTAssertNil(IPAddress.multicast_embedded_rp(ip("2001:db8::1:3/64"), 1, MulticastScope.global));
+    XCTAssertNil(IPAddress.multicast_embedded_rp(ip("2001:db8::3/96"), 1, MulticastScope.global));
+    XCTAssertNil(IPAddress.multicast_embedded_rp(ip("10.0.0.3/8"), 1, MulticastScope.global));
+  }
+
+  func test_method_solicited_node() {
+    XCTAssertEqual("ff02::1:ff02:3/128", ip("2001:db8::1:2:3/64").solicited_node()!.to_string());
+    XCTAssertEqual("ff02::1:ff00:1%eth0", ip("fe80::1%eth0").solicited_node()!.to_s());
+    XCTAssertEqual("ff02::1:ff00:0", ip("::").solicited_node()!.to_s());
+    XCTAssertNil(ip("ff02::1").solicited_node());
+    XCTAssertNil(ip("10.0.0.1").solicited_node());
+  }
+
+  func test_method_multicast_mac() {
+    XCTAssertEqual("01:00:5e:01:02:03", ip("239.1.2.3").multicast_mac());
+    XCTAssertEqual("01:00:5e:01:02:03", ip("224.129.2.3").multicast_mac());
+    XCTAssertEqual("01:00:5e:7f:ff:fa", ip("239.255.255.250").multicast_mac());
+    XCTAssertEqual("33:33:00:00:00:01", ip("ff02::1").multicast_mac());
+    XCTAssertEqual("33:33:ff:02:00:03", ip("2001:db8::1:2:3").solicited_node()!.multicast_mac());
+    XCTAssertNil(ip("10.0.0.1").multicast_mac());
+    XCTAssertNil(ip("fe80::1").multicast_mac());
+  }
+
+  static var allTests : [(String, (MulticastTests) -> () throws -> Void)] {
+    return [
+      ("test_method_multicast_scope", test_method_multicast_scope),
+      ("test_method_multicast_flags", test_method_multicast_flags),
+      ("test_unicast_prefix_based", test_unicast_prefix_based),
+      ("test_embedded_rp", test_embedded_rp),
+      ("test_method_solicited_node", test_method_solicited_node),
+      ("test_method_multicast_mac", test_method_multicast_mac),
+    ]
+  }
+}
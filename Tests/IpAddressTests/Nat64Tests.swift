@@ -0,0 +1,98 @@
+import XCTest
+@testable import IpAddress
+
+class Nat64Tests : XCTestCase {
+
+  // RFC 6052 section 2.4
+  let rfc_examples: [(String, String)] = [
+    ("2001:db8::/32", "2001:db8:c000:221::"),
+    ("2001:db8:100::/40", "2001:db8:1c0:2:21::"),
+    ("2001:db8:122::/48", "2001:db8:122:c000:2:2100::"),
+    ("2001:db8:122:300::/56", "2001:db8:122:3c0:0:221::"),
+    ("2001:db8:122:344::/64", "2001:db8:122:344:c0:2:2100:0"),
+    ("2001:db8:122:344::/96", "2001:db8:122:344::c000:221"),
+  ];
+
+  func test_method_to_nat64() {
+    let ipv4 = ip("192.0.2.33");
+    for (prefix, expected) in rfc_examples {
+      let embedded = ipv4.to_nat64(ip(prefix))!;
+      XCTAssertEqual(expected, embedded.to_s_canonical(), prefix);
+      XCTAssertEqual(128, embedded.prefix.num);
+    }
+    XCTAssertEqual("64:ff9b::c000:221/128", ipv4.to_nat64()!.to_string());
+    XCTAssertEqual("64:ff9b::192.0.2.33", ipv4.to_nat64()!.format(FormatOptions(embedded_ipv4: true, prefix: PrefixFormat.none)));
+    XCTAssertEqual("2001:db8:122:344::192.0.2.33/128",
+                   ipv4.to_nat64(ip("2001:db8:122:344::/96"))!.format(FormatOptions(embedded_ipv4: true)));
+    XCTAssertEqual("2001:db8:c000:221::/128", ipv4.to_nat64(ip("2001:db8::1/32"))!.to_string());
+    XCTAssertNil(ipv4.to_nat64(ip("2001:db8::/33")));
+    XCTAssertNil(ipv4.to_nat64(ip("2001:db8::/128")));
+    XCTAssertNil(ipv4.to_nat64(ip("10.0.0.0/8")));
+    XCTAssertNil(ip("2001:db8::1").to_nat64());
+  }
+
+  func test_network_prefix() {
+    let net = ip("192.0.2.0/24");
+    XCTAssertEqual("64:ff9b::c000:200/120", net.to_nat64()!.to_string());
+    XCTAssertEqual("2001:db8:c000:200::/56", net.to_nat64(ip("2001:db8::/32"))!.to_string());
+    XCTAssertEqual("2001:db8:1c0:2::/64", net.to_nat64(ip("2001:db8:100::/40"))!.to_string());
+    XCTAssertEqual("2001:db8:122:c000:2::/80", net.to_nat64(ip("2001:db8:122::/48"))!.to_string());
+    XCTAssertEqual("2001:db8:122:344::/64", ip("0.0.0.0/0").to_nat64(ip("2001:db8:122:344::/64"))!.to_string());
+    XCTAssertEqual("192.0.2.0/24", ip("2001:db8:122:c000:2::/80").nat64_ipv4(ip("2001:db8:122::/48"))!.to_string());
+    XCTAssertEqual("192.0.2.0/24", ip("2001:db8:1c0:2::/64").nat64_ipv4(ip("2001:db8:100::/40"))!.to_string());
+    XCTAssertNil(ip("2001:db8:122:c000::/68").nat64_ipv4(ip("2001:db8:122::/48")));
+  }
+
+  func test_method_nat64_ipv4() {
+    for (prefix, embedded) in rfc_examples {
+      XCTAssertEqual("192.0.2.33/32", ip(embedded).nat64_ipv4(ip(prefix))!.to_string(), prefix);
+      XCTAssertTrue(ip(embedded).is_nat64(ip(prefix)));
+    }
+    XCTAssertEqual("192.0.2.33/32", ip("64:ff9b::192.0.2.33").nat64_ipv4()!.to_string());
+    XCTAssertEqual("192.0.2.32/31", ip("64:ff9b::c000:220/127").nat64_ipv4()!.to_string());
+    // the u-octet must be zero
+    XCTAssertNil(ip("2001:db8:122:344:1c0:2:2100:0").nat64_ipv4(ip("2001:db8:122:344::/64")));
+    XCTAssertNil(ip("2001:db8:c000:221:100::").nat64_ipv4(ip("2001:db8::/32")));
+    XCTAssertNil(ip("2001:db9:c000:221::").nat64_ipv4(ip("2001:db8::/32")));
+    XCTAssertNil(ip("64:ff9b:1::c000:221").nat64_ipv4());
+    XCTAssertNil(ip("2001:db8::c000:221").nat64_ipv4(ip("2001:db8::/33")));
+    XCTAssertNil(ip("192.0.2.33").nat64_ipv4());
+    XCTAssertFalse(ip("2001:db8::1").is_nat64());
+  }
+
+  func test_round_trip() {
+    let ipv4 = ip("203.0.113.254");
+    for plen in IPAddress.nat64_prefix_lengths {
+      let prefix = ip("2001:db8:aaaa:bbbb::/\(plen)");
+      XCTAssertEqual(ipv4, ipv4.to_nat64(prefix)!.nat64_ipv4(prefix), "/\(plen)");
+    }
+  }
+
+  func test_parse_embedded_ipv4() {
+    XCTAssertEqual("64:ff9b::c000:221/128", ip("64:ff9b::192.0.2.33").to_string());
+    XCTAssertEqual("64:ff9b::c000:200/120", ip("64:ff9b::192.0.2.0/120").to_string());
+    XCTAssertEqual("1:2:3:4:5:6:a00:1/128", ip("1:2:3:4:5:6:10.0.0.1").to_string());
+    XCTAssertEqual("fe80::c000:221%eth0", ip("fe80::192.0.2.33%eth0").to_s());
+    XCTAssertEqual("64:ff9b::c000:221/128", try! Ipv6.create("64:ff9b::192.0.2.33").to_string());
+    // ::ffff:a.b.c.d keeps the IPv4 prefix
+    XCTAssertEqual("::ffff:c000:200/120", ip("::ffff:192.0.2.0/24").to_string());
+    XCTAssertThrowsError(try IPAddress.parse("1:2:3:4:5:6:7:10.0.0.1"));
+    XCTAssertThrowsError(try IPAddress.parse("1::2:3:4:5:6:10.0.0.1"));
+    XCTAssertThrowsError(try IPAddress.parse("64:ff9b::192.0.2.256")) { err in
+      XCTAssertEqual(IPAddressParseError.octet_out_of_range("256", 17), err as? IPAddressParseError);
+    }
+    XCTAssertThrowsError(try IPAddress.parse("64:ff9b::192.0.2.1/129")) { err in
+      XCTAssertEqual(IPAddressParseError.prefix_out_of_range("129", 19), err as? IPAddressParseError);
+    }
+  }
+
+  static var allTests : [(String, (Nat64Tests) -> () throws -> Void)] {
+    return [
+      ("test_method_to_nat64", test_method_to_nat64),
+      ("test_network_prefix", test_network_prefix),
+      ("test_method_nat64_ipv4", test_method_nat64_ipv4),
+      ("test_round_trip", test_round_trip),
+      ("test_parse_embedded_ipv4", test_parse_embedded_ipv4),
+    ]
+  }
+}
@@ -0,0 +1,70 @@
+import XCTest
+@testable import IpAddress
+
+class ParseErrorTests : XCTestCase {
+  
+  func parse_error(_ str: String) -> IPAddressParseError? {
+    do {
+      _ = try IPAddress.parse(str);
+    } catch let err as IPAddressParseError {
+      return err;
+    } catch {
+      return nil;
+    }
+    return nil;
+  }
+  
+  func test_ipv4_errors() {
+    XCTAssertEqual(IPAddressParseError.octet_out_of_range("256", 7), parse_error("10.0.0.256"));
+    XCTAssertEqual(IPAddressParseError.octet_not_decimal("a", 3), parse_error("10.a.0.1/24"));
+    XCTAssertEqual(IPAddressParseError.empty_octet("", 3), parse_error("10..0.1"));
+    XCTAssertEqual(IPAddressParseError.too_many_octets("5", 8), parse_error("1.2.3.4.5"));
+    XCTAssertEqual(IPAddressParseError.octet_out_of_range("300", 8), parse_error("  1.2.3.300"));
+  }
+  
+  func test_ipv6_errors() {
+    XCTAssertEqual(IPAddressParseError.multiple_double_colons("::", 4), parse_error("1::2::3"));
+    XCTAssertEqual(IPAddressParseError.empty_group("", 6), parse_error("2002:::1"));
+    XCTAssertEqual(IPAddressParseError.group_not_hex("xyz", 5), parse_error("2001:xyz::1"));
+    XCTAssertEqual(IPAddressParseError.group_out_of_range("10000", 5), parse_error("2001:10000::1"));
+    XCTAssertEqual(IPAddressParseError.group_not_hex("+db8", 5), parse_error("2001:+db8::1"));
+    XCTAssertEqual(IPAddressParseError.group_not_hex("-0", 5), parse_error("2001:-0::1"));
+    XCTAssertEqual(IPAddressParseError.group_not_hex("\u{ff11}", 5), parse_error("2001:\u{ff11}::1"));
+    XCTAssertEqual(IPAddressParseError.group_out_of_range("00001", 5), parse_error("2001:00001::1"));
+    XCTAssertEqual(IPAddressParseError.group_out_of_range("10000000000000000", 5),
+                   parse_error("2001:10000000000000000::1"));
+    XCTAssertEqual(IPAddressParseError.wrong_group_count("1:2:3", 0), parse_error("1:2:3"));
+    XCTAssertEqual(IPAddressParseError.too_many_groups("9", 16), parse_error("1:2:3:4:5:6:7:8:9"));
+  }
+  
+  func test_prefix_errors() {
+    XCTAssertEqual(IPAddressParseError.invalid_prefix("asd", 9), parse_error("10.0.0.0/asd"));
+    XCTAssertEqual(IPAddressParseError.prefix_out_of_range("33", 9), parse_error("10.0.0.0/33"));
+    XCTAssertEqual(IPAddressParseError.prefix_out_of_range("129", 4), parse_error("::1/129"));
+    XCTAssertEqual(IPAddressParseError.non_contiguous_netmask("255.0.255.0", 9),
+                   parse_error("10.0.0.1/255.0.255.0"));
+    XCTAssertEqual(IPAddressParseError.invalid_prefix("/8", 10), parse_error("10.0.0.1/8/8"));
+  }
+  
+  func test_unknown_format() {
+    XCTAssertEqual(IPAddressParseError.unknown_format("hello", 0), parse_error("hello"));
+    XCTAssertEqual(IPAddressParseError.unknown_format("", 0), parse_error(""));
+  }
+  
+  func test_description() {
+    let err = parse_error("10.0.0.256/24")!;
+    XCTAssertEqual("256", err.text);
+    XCTAssertEqual(7, err.offset);
+    XCTAssertEqual("octet out of range '256' at column 8", err.description);
+  }
+  
+  static var allTests : [(String, (ParseErrorTests) -> () throws -> Void)] {
+    return [
+      ("test_ipv4_errors", test_ipv4_errors),
+      ("test_ipv6_errors", test_ipv6_errors),
+      ("test_prefix_errors", test_prefix_errors),
+      ("test_unknown_format", test_unknown_format),
+      ("test_description", test_description),
+    ]
+  }
+}
@@ -39,11 +39,22 @@ class Prefix128Tests : XCTestCase {
     }
   }
   
+  func test_conformances() {
+    let prefix = Prefix128.create(64)!;
+    XCTAssertEqual("ffff:ffff:ffff:ffff::", prefix.description);
+    XCTAssertEqual("<Prefix128:64>", prefix.debugDescription);
+    XCTAssertEqual(prefix, Prefix(prefix.description));
+    XCTAssertEqual(0, Prefix("::")!.num);
+    XCTAssertNil(Prefix("ffff::ffff"));
+    XCTAssertEqual([prefix: "lan"][Prefix128.create(64)!], "lan");
+  }
+  
   static var allTests : [(String, (Prefix128Tests) -> () throws -> Void)] {
     return [
       ("test_initialize", test_initialize),
       ("test_method_bits", test_method_bits),
       ("test_method_to_u32", test_method_to_u32),
+      ("test_conformances", test_conformances),
     ]
   }
 }
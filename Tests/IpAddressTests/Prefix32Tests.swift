@@ -55,7 +55,7 @@ class Prefix32Tests: XCTestCase {
   func test_parse_netmask_to_prefix() {
     for (netmask, num) in setup().prefix_hash {
       // console.log(e);
-      let prefix = IPAddress.parse_netmask_to_prefix(netmask)!;
+      let prefix = try! IPAddress.parse_netmask_to_prefix(netmask);
       XCTAssertEqual(num, prefix);
     }
   }
@@ -116,6 +116,20 @@ class Prefix32Tests: XCTestCase {
     XCTAssertEqual("0.255.255.255", Ipv4.from_int(prefix.host_mask(), 0)!.to_s());
   }
   
+  func test_conformances() {
+    let prefix = Prefix32.create(24)!;
+    XCTAssertEqual("255.255.255.0", prefix.description);
+    XCTAssertEqual("<Prefix32:24>", prefix.debugDescription);
+    XCTAssertEqual(prefix, Prefix("255.255.255.0"));
+    XCTAssertNil(Prefix("255.0.255.0"));
+    XCTAssertNil(Prefix("255.255.255.0/24"));
+    XCTAssertNil(Prefix("24"));
+    XCTAssertTrue(Prefix32.create(8)! < prefix);
+    XCTAssertTrue(prefix < Prefix128.create(8)!);
+    XCTAssertEqual(Set([prefix, Prefix32.create(24)!, Prefix128.create(24)!]).count, 2);
+    XCTAssertEqual([8, 16, 24], [prefix, Prefix32.create(8)!, Prefix32.create(16)!].sorted().map({ $0.num }));
+  }
+  
   static var allTests : [(String, (Prefix32Tests) -> () throws -> Void)] {
     return [
       ("test_attributes", test_attributes),
@@ -130,6 +144,7 @@ class Prefix32Tests: XCTestCase {
       ("test_method_octets", test_method_octets),
       ("test_method_brackets", test_method_brackets),
       ("test_method_hostmask", test_method_hostmask),
+      ("test_conformances", test_conformances),
     ]
   }
 }
@@ -0,0 +1,114 @@
+import XCTest
+import BigInt
+@testable import IpAddress
+
+//  Runs the API on every prefix length of both families, at
+//  the bottom, the top and the middle of the address space, to
+//  prove that nothing overflows on /0 or on host networks.
+class PrefixLengthTests: XCTestCase {
+
+  func each_prefix(_ fn: (IPAddress) -> Void) {
+    for ip_bits in [IpBits.v4(), IpBits.v6()] {
+      let addresses = [UInt128.zero, ip_bits.all_ones, ip_bits.all_ones >> 1 & UInt128(hi: 0xa5a5a5a5a5a5a5a5, lo: 0xa5a5a5a5a5a5a5a5)];
+      for num in 0...ip_bits.bits {
+        let prefix = Prefix(num: num, ip_bits: ip_bits, net_mask: Prefix.new_netmask(num, ip_bits.bits));
+        for address in addresses {
+          fn(IPAddress(ip_bits: ip_bits, address: address, prefix: prefix));
+        }
+      }
+    }
+  }
+
+  func test_network() {
+    each_prefix({ ip in
+      let bits = ip.ip_bits.bits;
+      XCTAssertTrue(ip.network().address <= ip.address);
+      XCTAssertTrue(ip.address <= ip.broadcast().address);
+      XCTAssertEqual(BigUInt(1) << Int(ip.prefix.host_prefix()), ip.size());
+      XCTAssertTrue(ip.includes(ip.first()));
+      XCTAssertTrue(ip.includes(ip.last()));
+      XCTAssertEqual(ip.prefix.num != bits && ip.address == ip.network().address, ip.is_network());
+      XCTAssertEqual(ip, IPAddress(ip.to_string()));
+      XCTAssertEqual(ip, IPAddress(ip.to_string_canonical()));
+      XCTAssertEqual(ip, IPAddress(ip.to_string_uncompressed()));
+      XCTAssertEqual(ip.prefix, ip.netmask().prefix);
+      XCTAssertNil(ip.change_prefix(bits + 1));
+      XCTAssertEqual(ip.prefix.num == 0, ip.supernet(0) == nil);
+      XCTAssertNil(ip.prefix.add(255));
+      XCTAssertEqual(2 * Int(ip.prefix.num) > Int(bits), ip.prefix.add_prefix(ip.prefix) == nil);
+      _ = ip.newprefix(255);
+      _ = ip.inc();
+      _ = ip.dec();
+    });
+  }
+
+  func test_collections() {
+    each_prefix({ ip in
+      let bits = ip.ip_bits.bits;
+      XCTAssertEqual(ip.size(), ip.addresses.size());
+      XCTAssertEqual(ip.network().address, ip.addresses[0].address);
+      XCTAssertEqual(ip.broadcast().address, ip.addresses[-1].address);
+      XCTAssertEqual(ip.broadcast().address, ip.addresses.reversed().first!.address);
+      if (!ip.hosts.isEmpty) {
+        XCTAssertEqual(ip.first().address, ip.hosts[0].address);
+        XCTAssertEqual(ip.last().address, ip.hosts[-1].address);
+      }
+      XCTAssertEqual(BigUInt(1), ip.subnets(ip.prefix.num)!.size());
+      XCTAssertEqual(ip.size(), ip.subnets(bits)!.size());
+      XCTAssertNil(ip.subnets(bits + 1));
+      XCTAssertEqual(ip.broadcast().address, ip.subnets(bits)![-1].address);
+      let last = ip.subnet(at: UInt128.mask(Int(ip.prefix.host_prefix())), prefix: bits)!;
+      XCTAssertEqual(ip.broadcast().address, last.address);
+      XCTAssertEqual(UInt128.mask(Int(ip.prefix.host_prefix())), ip.index(of: last));
+      XCTAssertEqual(ip.size() > BigUInt(Int.max), ip.subnet(bits) == nil);
+      XCTAssertEqual(ip.prefix.num == bits ? 1 : 2, ip.subnet(min(ip.prefix.num + 1, bits))!.count);
+    });
+  }
+
+  func test_split() {
+    each_prefix({ ip in
+      XCTAssertEqual(ip.size() <= BigUInt(2), ip.split(2) == nil);
+      XCTAssertEqual(ip.size() <= BigUInt(3), ip.split(3) == nil);
+      XCTAssertNil(ip.split(0));
+      XCTAssertNil(ip.split(UInt.max));
+      if (ip.size() > BigUInt(3)) {
+        XCTAssertEqual(3, ip.split(3)!.count);
+        XCTAssertEqual(IPAddress.to_string_vec([ip.network()]),
+                       IPAddress.to_string_vec(IPAddress.aggregate(ip.split(3)!)));
+      }
+    });
+  }
+
+  func test_dns() {
+    each_prefix({ ip in
+      XCTAssertFalse(ip.dns_reverse().isEmpty);
+      for net in ip.dns_networks() {
+        XCTAssertTrue(net.includes(ip) || ip.includes(net));
+      }
+      XCTAssertEqual(ip.dns_networks().count, ip.dns_rev_domains().count);
+    });
+  }
+
+  func test_aggregate() {
+    each_prefix({ ip in
+      let universe = ip.change_prefix(0)!;
+      XCTAssertEqual([universe.network()], IPAddress.aggregate([ip, universe]));
+      XCTAssertEqual([ip.network()], IPAddress.aggregate([ip, ip.first(), ip.last()]));
+      XCTAssertEqual([ip.network()], IPRange(ip).networks());
+      XCTAssertEqual(ip.size(), IPRange(ip).size());
+      let rest = IPAddressSet([ip]).complement(universe);
+      XCTAssertEqual(universe.size(), rest.size() + ip.size());
+      XCTAssertFalse(rest.includes(ip));
+    });
+  }
+
+  static var allTests : [(String, (PrefixLengthTests) -> () throws -> Void)] {
+    return [
+      ("test_network", test_network),
+      ("test_collections", test_collections),
+      ("test_split", test_split),
+      ("test_dns", test_dns),
+      ("test_aggregate", test_aggregate),
+    ]
+  }
+}
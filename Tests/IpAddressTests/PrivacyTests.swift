@@ -0,0 +1,87 @@
+import XCTest
+@testable import IpAddress
+
+struct SequenceGenerator : RandomNumberGenerator {
+  var values: [UInt64];
+
+  mutating func next() -> UInt64 {
+    return self.values.removeFirst();
+  }
+}
+
+class PrivacyTests : XCTestCase {
+
+  let key: [UInt8] = (0..<16).map({ UInt8($0) });
+
+  func hex(_ bytes: [UInt8]) -> String {
+    return bytes.map({ String(UInt16($0) | 0x100, radix: 16).dropFirst(1) }).joined();
+  }
+
+  func test_sha256() {
+    XCTAssertEqual("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                   hex(Sha256.digest([])));
+    XCTAssertEqual("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                   hex(Sha256.digest(Array("abc".utf8))));
+    XCTAssertEqual("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+                   hex(Sha256.digest(Array("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq".utf8))));
+    XCTAssertEqual("41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3",
+                   hex(Sha256.digest([UInt8](repeating: 0x61, count: 1000))));
+  }
+
+  func test_method_is_reserved_interface_id() {
+    for iid: UInt64 in [0, 0x02005efffe000000, 0x02005efffe005213, 0x02005efffeffffff,
+                        0xfdffffffffffff80, 0xfdffffffffffffff] {
+      XCTAssertTrue(Ipv6.is_reserved_interface_id(iid), String(iid, radix: 16));
+    }
+    for iid: UInt64 in [1, 0x02005efffd000000, 0x02005effff000000, 0x00005efffe000000,
+                        0xfdffffffffffff7f, 0xfe00000000000000, 0xffffffffffffffff,
+                        0x021a2bfffe3c4d5e] {
+      XCTAssertFalse(Ipv6.is_reserved_interface_id(iid), String(iid, radix: 16));
+    }
+  }
+
+  func test_method_stable_privacy() {
+    let net = ip("2001:db8:1:2::/64");
+    XCTAssertEqual("2001:db8:1:2:d4d9:22:8d78:1ba9/64",
+                   Ipv6.stable_privacy(net, interface: "eth0", secret_key: key)!.to_string());
+    XCTAssertEqual("2001:db8:1:2:d4d9:22:8d78:1ba9/64",
+                   Ipv6.stable_privacy(ip("2001:db8:1:2::99/64"), interface: "eth0", secret_key: key)!.to_string());
+    XCTAssertEqual("2001:db8:1:2:c94e:78f0:ad3c:7307/64",
+                   Ipv6.stable_privacy(net, interface: "eth0", dad_counter: 1, secret_key: key)!.to_string());
+    XCTAssertEqual("2001:db8:1:2:ad9d:e411:119a:90d0/64",
+                   Ipv6.stable_privacy(net, interface: "wlan0", network_id: "lab-ssid", secret_key: key)!.to_string());
+    XCTAssertEqual("fe80::db87:e301:829a:9cac%eth0/64",
+                   Ipv6.stable_privacy(ip("fe80::%eth0/64"), interface: "eth0", secret_key: key)!.to_string());
+    XCTAssertNotEqual(Ipv6.stable_privacy(net, interface: "eth0", secret_key: key),
+                      Ipv6.stable_privacy(net, interface: "eth0", secret_key: [UInt8](repeating: 0, count: 16)));
+    XCTAssertNil(Ipv6.stable_privacy(ip("2001:db8::/96"), interface: "eth0", secret_key: key));
+    XCTAssertNil(Ipv6.stable_privacy(try! IPAddress.parse("10.0.0.0/8"), interface: "eth0", secret_key: key));
+  }
+
+  func test_method_temporary() {
+    var generator = SequenceGenerator(values: [0, 0xfdffffffffffff90, 0x02005efffe005213, 0x1234]);
+    XCTAssertEqual("2001:db8:1:2::1234/64", Ipv6.temporary(ip("2001:db8:1:2::/64"), using: &generator)!.to_string());
+    XCTAssertTrue(generator.values.isEmpty);
+    generator = SequenceGenerator(values: [0x8877665544332211]);
+    XCTAssertEqual("fe80::8877:6655:4433:2211%wlan0/64",
+                   Ipv6.temporary(ip("fe80::%wlan0/64"), using: &generator)!.to_string());
+    generator = SequenceGenerator(values: [0xfe00000000000000]);
+    XCTAssertEqual("2001:db8:1:2:fe00::/64", Ipv6.temporary(ip("2001:db8:1:2::/64"), using: &generator)!.to_string());
+    XCTAssertNil(Ipv6.temporary(ip("2001:db8::/80"), using: &generator));
+    let net = ip("2001:db8:1:2::/64");
+    for _ in 0..<16 {
+      let tmp = Ipv6.temporary(net)!;
+      XCTAssertTrue(net.includes(tmp));
+      XCTAssertFalse(Ipv6.is_reserved_interface_id(tmp.interface_id()!));
+    }
+  }
+
+  static var allTests : [(String, (PrivacyTests) -> () throws -> Void)] {
+    return [
+      ("test_sha256", test_sha256),
+      ("test_method_is_reserved_interface_id", test_method_is_reserved_interface_id),
+      ("test_method_stable_privacy", test_method_stable_privacy),
+      ("test_method_temporary", test_method_temporary),
+    ]
+  }
+}
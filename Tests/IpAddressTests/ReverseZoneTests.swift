@@ -0,0 +1,145 @@
+import XCTest
+@testable import IpAddress
+
+class ReverseZoneTests : XCTestCase {
+
+  let options = ReverseZoneOptions(primary: "ns1.example.com", contact: "host.master@example.com",
+                                   name_servers: ["ns1.example.com", "ns2.example.com."],
+                                   serial: 2024101601);
+
+  func test_method_reverse_zones() {
+    let hosts = [
+      ip("192.0.2.10"): "www.example.com",
+      ip("192.0.2.1/24"): "router.example.com.",
+      ip("192.0.3.7"): "mail.example.com",
+      ip("198.51.100.1"): "other.example.com",
+      ip("2001:db8::1"): "v6.example.com",
+    ];
+    let zones = ip("192.0.2.0/23").reverse_zones(hosts);
+    XCTAssertEqual(["2.0.192.in-addr.arpa.", "3.0.192.in-addr.arpa."], zones.map({ $0.origin }));
+    XCTAssertEqual(["192.0.2.0/24", "192.0.3.0/24"], zones.map({ $0.network.to_string() }));
+    XCTAssertEqual(["1", "10"], zones[0].records.map({ $0.name }));
+    XCTAssertEqual(["router.example.com.", "www.example.com"], zones[0].records.map({ $0.host }));
+    XCTAssertEqual("192.0.2.1/32", zones[0].records[0].address.to_string());
+    XCTAssertEqual(["7"], zones[1].records.map({ $0.name }));
+    XCTAssertEqual(["0.192.in-addr.arpa."], ip("192.0.0.0/16").reverse_zones(hosts).map({ $0.origin }));
+    XCTAssertEqual(["1.2", "10.2", "7.3"], ip("192.0.0.0/16").reverse_zones(hosts)[0].records.map({ $0.name }));
+    let small = ip("192.0.2.8/30").reverse_zones(hosts);
+    XCTAssertEqual(4, small.count);
+    XCTAssertEqual(["@"], small[2].records.map({ $0.name }));
+    XCTAssertEqual("10.2.0.192.in-addr.arpa.", small[2].origin);
+    let v6 = ip("2001:db8::/63").reverse_zones(hosts);
+    XCTAssertEqual(["0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa.", "1.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa."],
+                   v6.map({ $0.origin }));
+    XCTAssertEqual(["1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0"], v6[0].records.map({ $0.name }));
+    XCTAssertTrue(v6[1].records.isEmpty);
+    XCTAssertTrue(ip("10.0.0.0/24").reverse_zones(hosts)[0].records.isEmpty);
+  }
+
+  func test_method_to_master_file() {
+    let zone = ip("192.0.2.0/24").reverse_zones([ip("192.0.2.1"): "router.example.com",
+                                                 ip("192.0.2.10"): "www.example.com"])[0];
+    XCTAssertEqual([
+      "$ORIGIN 2.0.192.in-addr.arpa.",
+      "$TTL 3600",
+      "@\tIN\tSOA\tns1.example.com. host\\.master.example.com. 2024101601 3600 900 604800 3600",
+      "@\tIN\tNS\tns1.example.com.",
+      "@\tIN\tNS\tns2.example.com.",
+      "1\tIN\tPTR\trouter.example.com.",
+      "10\tIN\tPTR\twww.example.com.",
+      "",
+    ].joined(separator: "\n"), zone.to_master_file(options));
+    let minimal = ReverseZoneOptions(primary: "ns.example.net.", contact: "admin.example.net", ttl: 300);
+    XCTAssertEqual([
+      "$ORIGIN 0.0.10.in-addr.arpa.",
+      "$TTL 300",
+      "@\tIN\tSOA\tns.example.net. admin.example.net. 1 3600 900 604800 3600",
+      "@\tIN\tNS\tns.example.net.",
+      "",
+    ].joined(separator: "\n"), ip("10.0.0.0/24").reverse_zones([:])[0].to_master_file(minimal));
+  }
+
+  func test_method_dns_classless_reverse() {
+    XCTAssertEqual("64/26.2.0.192.in-addr.arpa", ip("192.0.2.64/26").dns_classless_reverse());
+    XCTAssertEqual("64/26.2.0.192.in-addr.arpa", ip("192.0.2.70/26").dns_classless_reverse());
+    XCTAssertEqual("64-26.2.0.192.in-addr.arpa", ip("192.0.2.64/26").dns_classless_reverse(ClasslessSeparator.dash));
+    XCTAssertEqual("128/25.2.0.192.in-addr.arpa", ip("192.0.2.200/25").dns_classless_reverse());
+    XCTAssertEqual("254/31.2.0.192.in-addr.arpa", ip("192.0.2.254/31").dns_classless_reverse());
+    XCTAssertNil(ip("192.0.2.0/24").dns_classless_reverse());
+    XCTAssertNil(ip("192.0.2.1/32").dns_classless_reverse());
+    XCTAssertNil(ip("2001:db8::/120").dns_classless_reverse());
+  }
+
+  func test_method_dns_classless_cnames() {
+    let cnames = ip("192.0.2.64/26").dns_classless_cnames()!;
+    XCTAssertEqual(64, cnames.count);
+    XCTAssertEqual(CnameRecord(name: "64", address: ip("192.0.2.64"), target: "64.64/26.2.0.192.in-addr.arpa."), cnames[0]);
+    XCTAssertEqual("65", cnames[1].name);
+    XCTAssertEqual("127.64/26.2.0.192.in-addr.arpa.", cnames[63].target);
+    for cname in cnames {
+      XCTAssertEqual(cname.address, try! IPAddress.from_dns_reverse(cname.target));
+    }
+    XCTAssertEqual(["2.0-31.2.0.192.in-addr.arpa.", "3.0-31.2.0.192.in-addr.arpa."],
+                   ip("192.0.2.2/31").dns_classless_cnames(ClasslessSeparator.dash)!.map({ $0.target }));
+    XCTAssertNil(ip("192.0.2.0/24").dns_classless_cnames());
+  }
+
+  func test_method_classless_reverse_zone() {
+    let hosts = [
+      ip("192.0.2.100"): "c.example.com",
+      ip("192.0.2.65"): "a.example.com",
+      ip("192.0.2.1"): "outside.example.com",
+    ];
+    let zone = ip("192.0.2.64/26").classless_reverse_zone(hosts)!;
+    XCTAssertEqual("64/26.2.0.192.in-addr.arpa.", zone.origin);
+    XCTAssertEqual("192.0.2.64/26", zone.network.to_string());
+    XCTAssertEqual(["65", "100"], zone.records.map({ $0.name }));
+    XCTAssertEqual([
+      "$ORIGIN 64/26.2.0.192.in-addr.arpa.",
+      "$TTL 3600",
+      "@\tIN\tSOA\tns1.example.com. host\\.master.example.com. 2024101601 3600 900 604800 3600",
+      "@\tIN\tNS\tns1.example.com.",
+      "@\tIN\tNS\tns2.example.com.",
+      "65\tIN\tPTR\ta.example.com.",
+      "100\tIN\tPTR\tc.example.com.",
+      "",
+    ].joined(separator: "\n"), zone.to_master_file(options));
+    XCTAssertEqual("64-26.2.0.192.in-addr.arpa.",
+                   ip("192.0.2.64/26").classless_reverse_zone(hosts, ClasslessSeparator.dash)!.origin);
+    XCTAssertNil(ip("192.0.2.0/24").classless_reverse_zone(hosts));
+  }
+
+  func test_classless_from_dns_reverse() {
+    XCTAssertEqual("192.0.2.64/26", try! IPAddress.from_dns_reverse("64/26.2.0.192.in-addr.arpa").to_string());
+    XCTAssertEqual("192.0.2.64/26", try! IPAddress.from_dns_reverse("64-26.2.0.192.IN-ADDR.ARPA.").to_string());
+    XCTAssertEqual("192.0.2.65/32", try! IPAddress.from_dns_reverse("65.64/26.2.0.192.in-addr.arpa").to_string());
+    XCTAssertEqual("192.0.2.128/25", try! IPAddress.from_dns_reverse("128/25.2.0.192.in-addr.arpa").to_string());
+    let errors: [(String, IPAddressParseError)] = [
+      ("65/26.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("65/26", 0)),
+      ("64/24.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("64/24", 0)),
+      ("64/32.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("64/32", 0)),
+      ("64/.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("64/", 0)),
+      ("64/26.2.0.in-addr.arpa", IPAddressParseError.invalid_reverse_label("64/26", 0)),
+      ("1.2.64/26.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("64/26", 4)),
+      ("200.64/26.2.0.192.in-addr.arpa", IPAddressParseError.invalid_reverse_label("200", 0)),
+      ("64/26.2.0.256.in-addr.arpa", IPAddressParseError.invalid_reverse_label("256", 10)),
+    ];
+    for (name, error) in errors {
+      XCTAssertThrowsError(try IPAddress.from_dns_reverse(name), name) { err in
+        XCTAssertEqual(error, err as? IPAddressParseError, name);
+      }
+    }
+    XCTAssertThrowsError(try IPAddress.from_dns_reverse("0/26.0.0.0.0.0.0.0.0.ip6.arpa"));
+  }
+
+  static var allTests : [(String, (ReverseZoneTests) -> () throws -> Void)] {
+    return [
+      ("test_method_reverse_zones", test_method_reverse_zones),
+      ("test_method_to_master_file", test_method_to_master_file),
+      ("test_method_dns_classless_reverse", test_method_dns_classless_reverse),
+      ("test_method_dns_classless_cnames", test_method_dns_classless_cnames),
+      ("test_method_classless_reverse_zone", test_method_classless_reverse_zone),
+      ("test_classless_from_dns_reverse", test_classless_from_dns_reverse),
+    ]
+  }
+}
@@ -0,0 +1,106 @@
+import XCTest
+@testable import IpAddress
+
+class SpecialPurposeTests : XCTestCase {
+
+  func test_registry() {
+    XCTAssertEqual(25, SpecialPurpose.ipv4_registry.count);
+    XCTAssertEqual(23, SpecialPurpose.ipv6_registry.count);
+    for entry in SpecialPurpose.ipv4_registry + SpecialPurpose.ipv6_registry {
+      XCTAssertTrue(entry.network.is_network() || entry.network.prefix.num == entry.network.ip_bits.bits);
+      XCTAssertEqual(entry.name, entry.network.special_purpose()!.name);
+    }
+  }
+
+  func test_method_special_purpose() {
+    let shared = ip("100.64.1.1").special_purpose()!;
+    XCTAssertEqual("Shared Address Space", shared.name);
+    XCTAssertEqual("RFC 6598", shared.rfc);
+    XCTAssertEqual("100.64.0.0/10", shared.network.to_string());
+    XCTAssertTrue(shared.source);
+    XCTAssertTrue(shared.destination);
+    XCTAssertTrue(shared.forwardable);
+    XCTAssertEqual(false, shared.globally_reachable);
+    XCTAssertFalse(shared.reserved_by_protocol);
+    XCTAssertEqual("Port Control Protocol Anycast", ip("192.0.0.9").special_purpose()!.name);
+    XCTAssertEqual("IETF Protocol Assignments", ip("192.0.0.99").special_purpose()!.name);
+    XCTAssertEqual("This host on this network", ip("0.0.0.0").special_purpose()!.name);
+    XCTAssertEqual("This network", ip("0.1.2.3").special_purpose()!.name);
+    XCTAssertEqual(["Reserved", "Limited Broadcast"],
+                   SpecialPurpose.lookup(ip("255.255.255.255")).map({ $0.name }));
+    XCTAssertEqual(["IETF Protocol Assignments", "TEREDO"],
+                   SpecialPurpose.lookup(ip("2001::1")).map({ $0.name }));
+    XCTAssertNil(ip("2001::1").special_purpose()!.globally_reachable);
+    XCTAssertEqual("Link-Local Unicast", ip("fe80::1%eth0").special_purpose()!.name);
+    XCTAssertTrue(ip("127.0.0.1").special_purpose()!.reserved_by_protocol);
+    XCTAssertFalse(ip("::1").special_purpose()!.source);
+    XCTAssertNil(ip("8.8.8.8").special_purpose());
+    XCTAssertNil(ip("224.0.0.1").special_purpose());
+    XCTAssertNil(ip("10.0.0.0/7").special_purpose());
+    XCTAssertNil(ip("2606:4700::1111").special_purpose());
+  }
+
+  func test_method_is_global() {
+    for str in ["8.8.8.8", "1.1.1.1", "192.0.0.9", "192.0.0.10", "192.31.196.1", "192.175.48.1",
+                "2606:4700::1111", "2001:1::1", "2001:20::1", "64:ff9b::808:808", "2002:c000:204::1"] {
+      XCTAssertTrue(ip(str).is_global(), str);
+    }
+    for str in ["10.1.1.1", "100.64.0.1", "127.0.0.1", "169.254.1.1", "172.16.0.1", "192.0.0.1",
+                "192.0.2.1", "192.168.1.1", "198.18.0.1", "240.0.0.1", "255.255.255.255", "0.0.0.0",
+                "::1", "::", "::ffff:8.8.8.8", "2001::1", "2001:db8::1", "3fff::1", "fc00::1",
+                "fd12:3456::1", "fe80::1", "64:ff9b:1::1", "100::1", "5f00::1"] {
+      XCTAssertFalse(ip(str).is_global(), str);
+    }
+  }
+
+  func test_classification() {
+    XCTAssertTrue(ip("169.254.10.1").is_link_local());
+    XCTAssertTrue(ip("fe80::1%eth0").is_link_local());
+    XCTAssertFalse(ip("fec0::1").is_link_local());
+    XCTAssertFalse(ip("169.255.0.1").is_link_local());
+    XCTAssertTrue(ip("192.0.2.1").is_documentation());
+    XCTAssertTrue(ip("198.51.100.0/24").is_documentation());
+    XCTAssertTrue(ip("203.0.113.255").is_documentation());
+    XCTAssertTrue(ip("2001:db8::1").is_documentation());
+    XCTAssertTrue(ip("3fff:fff::1").is_documentation());
+    XCTAssertFalse(ip("198.51.0.0/16").is_documentation());
+    XCTAssertTrue(ip("100.64.0.1").is_shared());
+    XCTAssertTrue(ip("100.127.255.255").is_shared());
+    XCTAssertFalse(ip("100.128.0.0").is_shared());
+    XCTAssertTrue(ip("198.19.255.1").is_benchmarking());
+    XCTAssertTrue(ip("2001:2::1").is_benchmarking());
+    XCTAssertFalse(ip("198.20.0.1").is_benchmarking());
+    XCTAssertTrue(ip("240.0.0.1").is_reserved());
+    XCTAssertTrue(ip("255.255.255.255").is_reserved());
+    XCTAssertTrue(ip("fec0::1").is_reserved());
+    XCTAssertFalse(ip("2001:db8::1").is_reserved());
+    XCTAssertFalse(ip("fc00::1").is_reserved());
+    XCTAssertFalse(ip("239.255.255.255").is_reserved());
+    XCTAssertTrue(ip("fc00::1").is_unique_local());
+    XCTAssertTrue(ip("fdff::1").is_unique_local());
+    XCTAssertFalse(ip("fe00::1").is_unique_local());
+    XCTAssertFalse(ip("10.0.0.1").is_unique_local());
+    XCTAssertTrue(ip("224.0.0.1").is_multicast());
+    XCTAssertTrue(ip("239.255.255.255").is_multicast());
+    XCTAssertTrue(ip("ff02::1").is_multicast());
+    XCTAssertFalse(ip("240.0.0.1").is_multicast());
+    XCTAssertFalse(ip("fe80::1").is_multicast());
+  }
+
+  func test_method_is_private() {
+    XCTAssertFalse(ip("169.254.1.1").is_private());
+    XCTAssertTrue(ip("fc00::1").is_private());
+    XCTAssertTrue(ip("fd00::1").is_private());
+    XCTAssertFalse(ip("fe80::1").is_private());
+  }
+
+  static var allTests : [(String, (SpecialPurposeTests) -> () throws -> Void)] {
+    return [
+      ("test_registry", test_registry),
+      ("test_method_special_purpose", test_method_special_purpose),
+      ("test_method_is_global", test_method_is_global),
+      ("test_classification", test_classification),
+      ("test_method_is_private", test_method_is_private),
+    ]
+  }
+}
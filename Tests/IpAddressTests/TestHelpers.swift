@@ -0,0 +1,7 @@
+import XCTest
+@testable import IpAddress
+
+//  Parses an address which the test knows to be valid
+func ip(_ str: String) -> IPAddress {
+  return try! IPAddress.parse(str);
+}
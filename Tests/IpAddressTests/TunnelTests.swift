@@ -0,0 +1,69 @@
+import XCTest
+@testable import IpAddress
+
+class TunnelTests : XCTestCase {
+
+  func test_6to4() {
+    XCTAssertEqual("2002:c000:201::/48", ip("192.0.2.1").to_6to4()!.to_string());
+    XCTAssertEqual("2002:c000:200::/40", ip("192.0.2.0/24").to_6to4()!.to_string());
+    XCTAssertEqual("2002::/16", ip("0.0.0.0/0").to_6to4()!.to_string());
+    XCTAssertNil(ip("2001:db8::1").to_6to4());
+    XCTAssertTrue(ip("2002:c000:201:1::1").is_6to4());
+    XCTAssertEqual("192.0.2.1/32", ip("2002:c000:201:1::1").six_to_four_ipv4()!.to_string());
+    XCTAssertEqual("8.8.8.8/32", ip("8.8.8.8").to_6to4()!.six_to_four_ipv4()!.to_string());
+    XCTAssertEqual("192.0.2.0/24", ip("2002:c000:200::/40").six_to_four_ipv4()!.to_string());
+    XCTAssertEqual("192.0.2.1/32", ip("2002:c000:201::/48").six_to_four_ipv4()!.to_string());
+    XCTAssertEqual("192.0.2.1/32", ip("2002:c000:201:ff00::/56").six_to_four_ipv4()!.to_string());
+    XCTAssertFalse(ip("2003::1").is_6to4());
+    XCTAssertFalse(ip("2000::/3").is_6to4());
+    XCTAssertNil(ip("2001:db8::1").six_to_four_ipv4());
+    XCTAssertNil(ip("192.0.2.1").six_to_four_ipv4());
+  }
+
+  func test_teredo() {
+    let teredo = ip("2001:0:4136:e378:8000:63bf:3fff:fdd2").teredo()!;
+    XCTAssertEqual(ip("65.54.227.120"), teredo.server);
+    XCTAssertEqual(ip("192.0.2.45"), teredo.client);
+    XCTAssertEqual(40000, teredo.port);
+    XCTAssertEqual(0x8000, teredo.flags);
+    XCTAssertTrue(teredo.is_cone());
+    XCTAssertEqual("2001:0:4136:e378:8000:63bf:3fff:fdd2", teredo.to_ipaddress()!.to_s_canonical());
+    XCTAssertEqual("2001:0:4136:e378:8000:63bf:3fff:fdd2/128",
+                   ip("192.0.2.45").to_teredo(ip("65.54.227.120"), 40000, 0x8000)!.to_string_canonical());
+    let plain = ip("10.1.2.3").to_teredo(ip("192.0.2.1"), 3544)!;
+    XCTAssertEqual("2001:0:c000:201:0:f227:f5fe:fdfc", plain.to_s_canonical());
+    XCTAssertEqual(Teredo(server: ip("192.0.2.1"), flags: 0, port: 3544, client: ip("10.1.2.3")), plain.teredo());
+    XCTAssertFalse(plain.teredo()!.is_cone());
+    XCTAssertNil(ip("10.1.2.3").to_teredo(ip("2001:db8::1"), 1));
+    XCTAssertNil(ip("2001:db8::1").to_teredo(ip("192.0.2.1"), 1));
+    XCTAssertNil(Teredo(server: ip("192.0.2.1"), flags: 0, port: 1, client: ip("::1")).to_ipaddress());
+    XCTAssertFalse(ip("2001:1::1").is_teredo());
+    XCTAssertNil(ip("2001:db8::1").teredo());
+    XCTAssertNil(ip("192.0.2.1").teredo());
+  }
+
+  func test_isatap() {
+    XCTAssertEqual("fe80::5efe:c0a8:101/64", ip("192.168.1.1").to_isatap(ip("fe80::/64"))!.to_string());
+    XCTAssertEqual("fe80::5efe:c0a8:101%eth0/64", ip("192.168.1.1").to_isatap(ip("fe80::%eth0/64"))!.to_string());
+    XCTAssertEqual("2001:db8::200:5efe:808:808/64", ip("8.8.8.8").to_isatap(ip("2001:db8::/64"))!.to_string());
+    XCTAssertNil(ip("8.8.8.8").to_isatap(ip("2001:db8::/96")));
+    XCTAssertNil(ip("8.8.8.8").to_isatap(ip("10.0.0.0/8")));
+    XCTAssertNil(ip("2001:db8::1").to_isatap(ip("fe80::/64")));
+    XCTAssertTrue(ip("fe80::5efe:c0a8:101").is_isatap());
+    XCTAssertEqual("192.168.1.1/32", ip("fe80::5efe:c0a8:101").isatap_ipv4()!.to_string());
+    XCTAssertEqual("8.8.8.8/32", ip("2001:db8::200:5efe:8.8.8.8").isatap_ipv4()!.to_string());
+    XCTAssertEqual("8.8.8.8/32", ip("2001:db8::300:5efe:808:808").isatap_ipv4()!.to_string());
+    XCTAssertFalse(ip("2001:db8::400:5efe:808:808").is_isatap());
+    XCTAssertFalse(ip("2001:db8::5eff:808:808").is_isatap());
+    XCTAssertNil(ip("fe80::1").isatap_ipv4());
+    XCTAssertNil(ip("0.0.94.254").isatap_ipv4());
+  }
+
+  static var allTests : [(String, (TunnelTests) -> () throws -> Void)] {
+    return [
+      ("test_6to4", test_6to4),
+      ("test_teredo", test_teredo),
+      ("test_isatap", test_isatap),
+    ]
+  }
+}
@@ -0,0 +1,59 @@
+import XCTest
+import BigInt
+@testable import IpAddress
+
+class UInt128Tests : XCTestCase {
+  
+  func test_big_round_trip() {
+    let big = BigUInt("338770000845734292534325025077361652240")!;
+    XCTAssertEqual(big, UInt128(big)!.big);
+    XCTAssertEqual(BigUInt(0), UInt128.zero.big);
+    XCTAssertEqual((BigUInt(1) << 128) - BigUInt(1), UInt128.max.big);
+    XCTAssertNil(UInt128(BigUInt(1) << 128));
+  }
+  
+  func test_shift() {
+    XCTAssertEqual(UInt128(hi: 1, lo: 0), UInt128.one << 64);
+    XCTAssertEqual(UInt128(hi: 0x8000000000000000, lo: 0), UInt128.one << 127);
+    XCTAssertEqual(UInt128.zero, UInt128.one << 128);
+    XCTAssertEqual(UInt128(hi: 0, lo: 0xffffffff00000000), UInt128(hi: 0xffffffff, lo: 0) >> 32);
+    XCTAssertEqual(UInt128(1), UInt128(hi: 1, lo: 0) >> 64);
+    XCTAssertEqual(UInt128.zero, UInt128.max >> 128);
+  }
+  
+  func test_arithmetic() {
+    XCTAssertEqual(UInt128(hi: 1, lo: 0), UInt128(hi: 0, lo: UInt64.max) + UInt128.one);
+    XCTAssertEqual(UInt128(hi: 0, lo: UInt64.max), UInt128(hi: 1, lo: 0) - UInt128.one);
+    XCTAssertEqual(UInt128.zero, UInt128.max &+ UInt128.one);
+    XCTAssertEqual(UInt128.max, UInt128.zero &- UInt128.one);
+    XCTAssertEqual(true, UInt128.max.addingReportingOverflow(UInt128.one).overflow);
+    XCTAssertEqual(UInt128(0xff), UInt128.mask(8));
+    XCTAssertEqual(UInt128.max, UInt128.mask(128));
+    XCTAssertEqual(UInt128.zero, UInt128.mask(0));
+  }
+  
+  func test_compare() {
+    XCTAssertTrue(UInt128(hi: 0, lo: UInt64.max) < UInt128(hi: 1, lo: 0));
+    XCTAssertFalse(UInt128(hi: 1, lo: 0) < UInt128(hi: 0, lo: 1));
+    XCTAssertEqual(127, UInt128(hi: 0, lo: 1).leadingZeroBitCount);
+    XCTAssertEqual(64, UInt128(hi: 1, lo: 0).trailingZeroBitCount);
+  }
+  
+  func test_to_string() {
+    XCTAssertEqual("0", UInt128.zero.to_string(16));
+    XCTAssertEqual("20010db80000000000080800200c417a",
+                   UInt128(hi: 0x20010db800000000, lo: 0x00080800200c417a).to_string(16));
+    XCTAssertEqual("340282366920938463463374607431768211455", UInt128.max.description);
+    XCTAssertEqual("101", UInt128(5).to_string(2));
+  }
+  
+  static var allTests : [(String, (UInt128Tests) -> () throws -> Void)] {
+    return [
+      ("test_big_round_trip", test_big_round_trip),
+      ("test_shift", test_shift),
+      ("test_arithmetic", test_arithmetic),
+      ("test_compare", test_compare),
+      ("test_to_string", test_to_string),
+    ]
+  }
+}
@@ -2,13 +2,27 @@ import XCTest
 @testable import IpAddressTests
 
 XCTMain([
+	testCase(CodableTests.allTests),
+	testCase(EuiTests.allTests),
+	testCase(IPAddressSetTests.allTests),
 	testCase(IPAddressTests.allTests),
+	testCase(IPPrefixMapTests.allTests),
+	testCase(IPRangeTests.allTests),
 	testCase(Ipv4Tests.allTests),
 	testCase(Ipv6LoopbackTests.allTests),
 	testCase(Ipv6MappedTests.allTests),
 	testCase(Ipv6Tests.allTests),
 	testCase(Ipv6UnspecTests.allTests),
+	testCase(MulticastTests.allTests),
+	testCase(Nat64Tests.allTests),
+	testCase(ParseErrorTests.allTests),
 	testCase(Prefix128Tests.allTests),
 	testCase(Prefix32Tests.allTests),
-	testCase(RleTests.allTests)
+	testCase(PrefixLengthTests.allTests),
+	testCase(PrivacyTests.allTests),
+	testCase(ReverseZoneTests.allTests),
+	testCase(RleTests.allTests),
+	testCase(SpecialPurposeTests.allTests),
+	testCase(TunnelTests.allTests),
+	testCase(UInt128Tests.allTests)
 ])